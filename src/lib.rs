@@ -1,10 +1,94 @@
 #![no_std]
-use soroban_sdk::{contract, contracttype, contractimpl, contractclient, Address, Env, Vec, Symbol, token};
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{contract, contracterror, contracttype, contractimpl, contractclient, Address, Bytes, Env, Map, Vec, Symbol, token};
 
 // --- DATA STRUCTURES ---
-const YIELD_LIQUIDITY_BUFFER_SECS: u64 = 60 * 60;
-const DURATION_CHANGE_NOTICE_SECS: u64 = 72 * 60 * 60;
+const CONTRACT_VERSION: u32 = 2; // Storage layout written by this code; see migrate
+// Defaults for the admin-adjustable ProtocolConfig
+const DEFAULT_YIELD_LIQUIDITY_BUFFER_SECS: u64 = 60 * 60;
+const DEFAULT_DURATION_CHANGE_NOTICE_SECS: u64 = 72 * 60 * 60;
+const DEFAULT_MIN_CYCLE_DURATION_SECS: u64 = 60 * 60;
 const REFERRAL_DISCOUNT_BPS: u32 = 500; // 5%
+const OPERATION_EXPIRY_SECS: u64 = 7 * 24 * 60 * 60;
+const PROPOSAL_EXPIRY_SECS: u64 = 7 * 24 * 60 * 60;
+const MAX_DEADLINE_EXTENSION_SECS: u64 = 7 * 24 * 60 * 60;
+const MAX_CYCLE_DURATION_SECS: u64 = 365 * 24 * 60 * 60;
+const CONTRIBUTION_HISTORY_LEN: u32 = 12;
+const INSURANCE_CLAIM_HISTORY_LEN: u32 = 100;
+const PENALTY_HISTORY_LEN: u32 = 50;
+const GOVERNANCE_HISTORY_LEN: u32 = 100;
+const MAX_TRUST_SCORE: u32 = 100;
+const ON_TIME_PAYER_DEPOSITS: u32 = 10; // On-time deposits, with none late, that earn the OnTimePayer badge
+const LONG_TERM_MEMBER_SECS: u64 = 365 * 24 * 60 * 60;
+const TRUST_SCORE_ON_TIME_GAIN: u32 = 5;
+const TRUST_SCORE_LATE_PENALTY: u32 = 10;
+const FROZEN_ROUNDS_BEFORE_EXIT: u32 = 2;
+const DEFAULT_PURGE_AFTER_SECS: u64 = 30 * 24 * 60 * 60;
+// 0 is allowed and means the circle runs without insurance
+const MAX_INSURANCE_FEE_BPS: u32 = 1000; // 10%
+const MIN_QUORUM_BPS: u32 = 5001; // Simple majority, the default
+const MIN_ZERO_YIELD_APPROVALS: u32 = 2; // Writing off pooled funds always needs a second admin
+const MIN_CONTRIBUTION_AMOUNT: u64 = 1;
+const MAX_CIRCLE_PAGE_SIZE: u64 = 50;
+const LEDGERS_PER_DAY: u32 = 17_280; // ~5s ledger close time
+const INSTANCE_TTL_THRESHOLD: u32 = 30 * LEDGERS_PER_DAY;
+const INSTANCE_TTL_EXTEND_TO: u32 = 365 * LEDGERS_PER_DAY;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SoroSusuError {
+    NotInitialized = 1,
+    Unauthorized = 2,
+    CircleNotFound = 3,
+    NotAMember = 4,
+    AlreadyMember = 5,
+    CircleFull = 6,
+    MemberNotActive = 7,
+    InvalidAmount = 8,
+    InvalidTier = 9,
+    InvalidBasisPoints = 10,
+    MaxMembersExceeded = 11,
+    InvalidCycleDuration = 12,
+    InvalidGracePeriod = 13,
+    AlreadyContributed = 14,
+    InsuranceAlreadyUsed = 15,
+    InsufficientBalance = 16,
+    SolvencyFloorBreached = 17,
+    ShareLimitExceeded = 18,
+    NoActiveProposal = 19,
+    InvalidReferrer = 20,
+    LendingPoolNotConfigured = 21,
+    OperationNotFound = 22,
+    OperationAlreadyExecuted = 23,
+    OperationExpired = 24,
+    OperationNotExpired = 25,
+    InvalidOperation = 26,
+    InvalidThreshold = 27,
+    RoundAlreadyFinalized = 28,
+    RoundIncomplete = 29,
+    NoPendingExit = 30,
+    ReserveEmpty = 31,
+    NoActiveMembers = 32,
+    InvalidAddress = 33,
+    InviteRequired = 34,
+    MemberNotFrozen = 35,
+    PurgeTooEarly = 36,
+    RoundNotFinalized = 37,
+    PayoutNotScheduled = 38,
+    PayoutTooEarly = 39,
+    MaxMembersBelowMemberCount = 40,
+    InvalidRecipientIndex = 41,
+    AddressBlocked = 42,
+    InvalidContributionBitmap = 43,
+    CircleNotComplete = 44,
+    CircleClosed = 45,
+    BatchLengthMismatch = 46,
+    TokenNotAccepted = 47,
+    ProposalExpired = 48,
+    ChangeAlreadyPending = 49,
+    ProtocolPaused = 50,
+}
 
 #[contracttype]
 #[derive(Clone)]
@@ -12,9 +96,10 @@ pub enum DataKey {
     Admin,
     LendingPool,
     Circle(u64),
+    // Keyed by address alone: an address holds a seat in at most one circle at a time
     Member(Address),
     CircleCount,
-    // New: Tracks if a user has paid for a specific circle (CircleID, UserAddress)
+    // New: Token a member paid this round's contribution in (CircleID, UserAddress); absent if insurance covered them
     Deposit(u64, Address),
     // New: Tracks pending exits (CircleID, MemberAddress)
     PendingExit(u64, Address),
@@ -24,12 +109,137 @@ pub enum DataKey {
     ScheduledPayoutTime(u64),
     // New: Tracks individual contributions for current round (CircleID, MemberIndex)
     CurrentRoundContribution(u64, u32),
+    // New: Reverse lookup from member address to the circle they belong to
+    MemberCircle(Address),
+    // New: Multi-sig admin set and number of approvals required to execute
+    AdminList,
+    ApprovalThreshold,
+    // New: Multi-sig operations by id, plus per-circle id list and open count
+    Operation(u64),
+    OperationCount,
+    CircleOperations(u64),
+    PendingOperationCount(u64),
+    // New: Circle ids created by each address, in creation order
+    CirclesByCreator(Address),
+    // New: Late-fee reserve scoped to a single circle
+    CircleReserve(u64),
+    // New: Active social-recovery proposal for a circle
+    RecoveryProposal(u64),
+    // New: Timestamps of a member's most recent deposits, oldest first
+    ContributionHistory(Address),
+    // New: Outstanding creator-issued invites (CircleID, Invitee)
+    Invite(u64, Address),
+    // New: When a member was ejected, and how long to wait before their entries can be purged
+    EjectionTime(Address),
+    PurgeAfterSecs,
+    // New: Pot collected in the last finalized round, held until the recipient claims it
+    RoundPot(u64),
+    // New: Running total of tokens taken in by deposits across all circles
+    TotalProtocolVolume,
+    // New: Lending pool ABI version the contract was configured against
+    LendingPoolVersion,
+    // New: Addresses barred from joining any circle
+    BlockedAddress(Address),
+    // New: Token each member deposits with, when not the circle's payout token
+    MemberToken(Address),
+    // New: Contributions made in tokens other than the payout token, for the open and the finalized round
+    RoundTokenContributions(u64),
+    RoundTokenPot(u64),
+    // New: When the open late-fee proposal of a circle stops accepting votes
+    ProposalExpiry(u64),
+    // New: Tier a member asked to move to (CircleID, Member), applied when the round is finalized
+    TierChangeRequest(u64, Address),
+    // New: Insurance pool of a circle, kept apart from CircleInfo so circle saves cannot overwrite it
+    InsuranceFund(u64),
+    // New: Number of rounds finalized in a circle
+    RoundNumber(u64),
+    // New: Global kill switch; while set, member and creator actions are refused
+    ProtocolPaused,
+    // New: Ledger timestamp at which a member took their seat
+    MemberJoinTime(Address),
+    // New: Copy of CircleInfo.member_count, so the count can be read without loading the circle
+    CircleMemberCount(u64),
+    // New: Next approval nonce of a multi-sig admin; each approval must present it and bumps it
+    OperationNonce(Address),
+    // New: Copy of CircleInfo.token, the payout token, for callers that need nothing else
+    CircleToken(u64),
+    // New: Lending pool a circle supplies to instead of the global LendingPool
+    YieldStrategy(u64),
+    // New: Ledger timestamp of a circle's most recent round finalization
+    LastRoundFinalizedAt(u64),
+    // New: Open vote on pushing back the current round's deposit deadline
+    ExtensionRequest(u64),
+    // New: Per-circle settings that do not fit in CircleInfo
+    CircleSettings(u64),
+    // New: (timestamp, beneficiary, amount) of each insurance payout in a circle, oldest first
+    InsuranceClaimHistory(u64),
+    // New: (new_bps, timestamp) of each late fee change that took effect in a circle, oldest first
+    PenaltyChangeHistory(u64),
+    // New: Outcome of each resolved late fee or insurance fee proposal, oldest first
+    GovernanceHistory(u64),
+    // New: Storage layout version; deployments initialized before it was recorded are version 1
+    ContractVersion,
+    // New: Protocol-wide timing parameters the admin may tune
+    ProtocolConfig,
+    // New: Lending pools the admin has vetted for use as a circle's yield strategy
+    ApprovedLendingPool(Address),
+}
+
+// Descriptive and analytics data that no payout or governance logic reads. Kept apart from DataKey, which is
+// at the 50-variant contracttype limit.
+#[contracttype]
+#[derive(Clone)]
+pub enum MetadataKey {
+    CircleName(u64),
+    CircleDescription(u64),
+    CircleCategory(u64),
+    // Ids of the circles in a category, in the order they were tagged
+    CategoryIndex(CircleCategory),
+    // How many members have ever joined naming this address as their referrer
+    ReferralCount(Address),
+    CircleStats(u64),
+    // Every address that has taken a seat in the circle, in the order they took it; never rewritten
+    JoinOrder(u64),
+    // Milestone badges a member has earned, in the order they were awarded
+    MemberBadges(Address),
+}
+
+// Per-circle round state added after DataKey reached the 50-variant contracttype limit
+#[contracttype]
+#[derive(Clone)]
+pub enum StateKey {
+    // Seats that paid the last finalized round on time; the late-fee reserve is split among them
+    OnTimePayers(u64),
+    // Votes on a contribution change are refused after this timestamp
+    ContributionProposalExpiry(u64),
+    // Set once a deadline extension passes; a round gets at most one
+    DeadlineExtended(u64),
+}
+
+// Milestones recognized with a badge; each is awarded at most once per member
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BadgeType {
+    OnTimePayer, // Ten deposits, none of them late
+    CircleCompleter, // Still active when the circle closed
+    LongTermMember, // Deposited a year or more after taking their seat
+}
+
+// What a circle is for; new circles start as Savings
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CircleCategory {
+    Savings,
+    Emergency,
+    Community,
+    Business,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub enum MemberStatus {
     Active,
+    Frozen,
     AwaitingReplacement,
     Ejected,
 }
@@ -41,26 +251,28 @@ pub struct Member {
     pub index: u32,
     pub contribution_count: u32,
     pub last_contribution_time: u64,
-    pub is_active: bool,
     pub tier_multiplier: u32, // Multiplier for tiered contributions (e.g., 1=Bronze, 2=Silver, 3=Gold)
     pub status: MemberStatus,
     pub total_contributed: u64,
     pub referrer: Option<Address>,
+    pub trust_score: u32, // 0-100, built up by on-time deposits and reduced by late ones
+    pub frozen_rounds: u32, // Rounds finalized while this member was frozen
+    pub emergency_contact: Option<Address>, // May start social recovery for this member
+    pub late_contributions: u32,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct AdminOperation {
     pub id: u64,
-    pub operation_type: u32, // 1=eject_member, 2=finalize_round, 3=trigger_insurance
+    pub operation_type: u32, // 1=eject_member, 2=finalize_round, 3=trigger_insurance, 4=zero_yield_deposited
     pub caller: Address,
     pub target_member: Option<Address>,
     pub circle_id: u64,
     pub approvals: Vec<Address>,
     pub created_at: u64,
+    pub expiry_timestamp: u64,
     pub is_executed: bool,
-    pub status: MemberStatus,
-    pub total_contributed: u64,
 }
 
 #[contracttype]
@@ -76,24 +288,109 @@ pub struct CircleInfo {
     pub token: Address, // The token used (USDC, XLM)
     pub deadline_timestamp: u64, // Deadline for on-time payments
     pub cycle_duration: u64, // Duration of each payment cycle in seconds
+    pub grace_period_secs: u64, // Window after the deadline before late fees apply
     pub pending_cycle_duration: u64,
     pub duration_change_effective_at: u64,
     pub contribution_bitmap: u64,
+    pub late_contribution_bitmap: u64, // Members who paid after the grace period this round
     pub payout_bitmap: u64,
-    pub insurance_balance: u64,
     pub insurance_fee_bps: u32,
     pub is_insurance_used: bool,
+    pub max_single_member_share_bps: u32, // Cap on one member's share of the pot (0 = disabled)
     pub late_fee_bps: u32,
     pub proposed_late_fee_bps: u32,
     pub proposal_votes_bitmap: u64,
+    pub proposed_contribution_amount: u64,
+    pub contribution_votes_bitmap: u64,
     pub nft_contract: Address,
+    pub nft_contract_version: u32, // Bumped every time the creator points the circle at a new NFT contract
     pub is_round_finalized: bool, // New: Track if round is finalized
     pub current_pot_recipient: Address, // New: Track who can claim the pot
     pub member_addresses: Vec<Address>, // New: Track member addresses for efficient lookup
     pub yield_deposited: u64,
-    pub recovery_old_address: Option<Address>,
-    pub recovery_new_address: Option<Address>,
-    pub recovery_votes_bitmap: u64,
+    pub refund_ejected_members: bool, // Return base contributions to members when they are ejected
+    pub priority_ordering: bool, // Pay the lowest trust score first instead of join order
+    pub is_invite_only: bool, // Joining requires an invite issued by the creator
+    pub yield_earned_this_round: u64, // Interest returned by the lending pool on top of yield_deposited
+    pub proposed_max_members: u32,
+    pub max_members_votes_bitmap: u64,
+    pub accepted_tokens: Vec<Address>, // Tokens members may deposit with; `token` is always among them and is the payout token
+    pub proposed_insurance_fee_bps: u32,
+    pub insurance_fee_votes_bitmap: u64, // Non-zero while an insurance fee proposal is open
+}
+
+// Protocol-wide timing parameters; every field is in seconds and must be between 1 and a year
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtocolConfig {
+    pub yield_liquidity_buffer_secs: u64, // Recall buffer for circles without their own, and the floor for those that set one
+    pub duration_change_notice_secs: u64, // Notice before a new cycle duration or contribution amount applies
+    pub min_cycle_duration_secs: u64, // Shortest cycle a new circle may use
+}
+
+// Per-circle state kept beside CircleInfo, which is at the 40-field contracttype limit
+#[contracttype]
+#[derive(Clone)]
+pub struct CircleSettings {
+    pub pending_contribution_amount: u64, // Zero when no change is scheduled
+    pub contribution_effective_at: u64,
+    pub quorum_threshold_bps: u32, // Share of members whose votes pass a fee or recovery proposal
+    pub created_at: u64, // Zero for circles created before it was recorded
+    pub max_yield_idle_secs: u64, // How long before the deadline deposits recall yield; zero follows ProtocolConfig
+}
+
+// Running totals over a circle's lifetime, for analytics
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CircleStats {
+    pub total_rounds_completed: u32,
+    pub total_volume: u128, // Everything members paid in: contributions, insurance fees and penalties
+    pub total_late_payments: u32,
+    pub total_insurance_triggers: u32,
+    pub total_members_joined: u32, // Includes replacements seated through fill_vacancy
+    pub total_members_ejected: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProposalType {
+    LateFee,
+    InsuranceFee,
+}
+
+// Snapshot of how a proposal was resolved; replaced, expired and overridden proposals are not approved
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoterTurnout {
+    pub proposal_type: ProposalType,
+    pub proposed_value: u32,
+    pub votes_cast: u32,
+    pub member_count: u32,
+    pub approved: bool,
+    pub resolved_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ExtensionRequest {
+    pub extension_secs: u64,
+    pub votes_bitmap: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct TierChangeRequest {
+    pub new_tier: u32,
+    pub approved: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RecoveryProposal {
+    pub old_address: Address,
+    pub new_address: Address,
+    pub votes_bitmap: u64,
+    pub proposed_at: u64, // Votes are refused PROPOSAL_EXPIRY_SECS after this
 }
 
 #[contracttype]
@@ -109,229 +406,1064 @@ pub struct GroupHealthUpdateEvent {
 
 pub trait SoroSusuTrait {
     // Initialize the contract
-    fn init(env: Env, admin: Address);
+    fn init(env: Env, admin: Address) -> Result<(), SoroSusuError>;
+
+    // Set the lending pool used for idle-fund yield strategy and the ABI version it must report (admin only)
+    fn set_lending_pool(env: Env, admin: Address, pool: Address, version: u32) -> Result<(), SoroSusuError>;
+
+    // Allow a lending pool to be chosen as a circle's yield strategy, and withdraw that (admin only)
+    fn approve_lending_pool(env: Env, admin: Address, pool: Address) -> Result<(), SoroSusuError>;
+    fn revoke_lending_pool(env: Env, admin: Address, pool: Address) -> Result<(), SoroSusuError>;
+    
+    // Bar an address from joining any circle, and lift the bar (admin only)
+    fn block_address(env: Env, admin: Address, target: Address) -> Result<(), SoroSusuError>;
+    fn unblock_address(env: Env, admin: Address, target: Address) -> Result<(), SoroSusuError>;
 
-    // Set the lending pool used for idle-fund yield strategy (admin only)
-    fn set_lending_pool(env: Env, admin: Address, pool: Address);
+    // Halt and restart all member and creator activity across every circle (admin only)
+    fn pause_protocol(env: Env, admin: Address) -> Result<(), SoroSusuError>;
+    fn resume_protocol(env: Env, admin: Address) -> Result<(), SoroSusuError>;
+
+    // Storage layout version, and bring stored data from one version to a later one after an upgrade (admin only)
+    fn get_contract_version(env: Env) -> u32;
+    fn migrate(env: Env, admin: Address, from_version: u32, to_version: u32) -> Result<(), SoroSusuError>;
+
+    // Protocol-wide timing parameters, and replacing them (admin only)
+    fn get_protocol_config(env: Env) -> ProtocolConfig;
+    fn set_protocol_config(env: Env, admin: Address, config: ProtocolConfig) -> Result<(), SoroSusuError>;
+
+    // Set how long ejected members' entries are kept before anyone may purge them (admin only)
+    fn set_purge_after_secs(env: Env, admin: Address, purge_after_secs: u64) -> Result<(), SoroSusuError>;
     
     // Create a new savings circle
-    fn create_circle(env: Env, creator: Address, amount: u64, max_members: u32, token: Address, cycle_duration: u64, insurance_fee_bps: u32, nft_contract: Address) -> u64;
+    fn create_circle(env: Env, creator: Address, amount: u64, max_members: u32, token: Address, cycle_duration: u64, grace_period_secs: u64, insurance_fee_bps: u32, max_single_member_share_bps: u32, nft_contract: Address) -> Result<u64, SoroSusuError>;
+
+    // Start a fresh circle with the same settings as an existing one; anyone may clone any circle
+    fn clone_circle(env: Env, creator: Address, source_circle_id: u64) -> Result<u64, SoroSusuError>;
+
+    // List the circles created by an address
+    fn get_circles_by_creator(env: Env, creator: Address) -> Result<Vec<u64>, SoroSusuError>;
+
+    // Ids of active circles among the `limit` ids starting at `start` (at most 50 scanned per call)
+    fn get_active_circles(env: Env, start: u64, limit: u64) -> Result<Vec<u64>, SoroSusuError>;
+
+    // Payout token of a circle, read without loading the whole circle
+    fn get_circle_token(env: Env, circle_id: u64) -> Result<Address, SoroSusuError>;
+
+    // Join an existing circle. An address sits in one circle at a time; after exiting or being
+    // ejected it may join another once purge_exited_member has removed its old entry
+    fn join_circle(env: Env, user: Address, circle_id: u64, tier_multiplier: u32) -> Result<(), SoroSusuError>;
+    fn join_circle_with_referrer(env: Env, user: Address, circle_id: u64, tier_multiplier: u32, referrer: Option<Address>) -> Result<(), SoroSusuError>;
 
-    // Join an existing circle
-    fn join_circle(env: Env, user: Address, circle_id: u64, tier_multiplier: u32);
-    fn join_circle_with_referrer(env: Env, user: Address, circle_id: u64, tier_multiplier: u32, referrer: Option<Address>);
+    // Seat a list of members with their tiers in one call (creator only); all or nothing
+    fn batch_join(env: Env, creator: Address, circle_id: u64, members: Vec<Address>, tier_multipliers: Vec<u32>) -> Result<(), SoroSusuError>;
+
+    // Restrict joining to invited addresses and manage invites (creator only)
+    fn set_invite_only(env: Env, creator: Address, circle_id: u64, enabled: bool) -> Result<(), SoroSusuError>;
+    fn issue_invite(env: Env, creator: Address, circle_id: u64, invitee: Address) -> Result<(), SoroSusuError>;
+    fn revoke_invite(env: Env, creator: Address, circle_id: u64, invitee: Address) -> Result<(), SoroSusuError>;
+
+    // Name and describe a circle for display (creator only); the name may not be empty
+    fn set_circle_description(env: Env, creator: Address, circle_id: u64, name: Symbol, description: Bytes) -> Result<(), SoroSusuError>;
+    fn get_circle_description(env: Env, circle_id: u64) -> Result<(Symbol, Bytes), SoroSusuError>;
+
+    // Tag a circle with its purpose (creator only) and look circles up by tag
+    fn set_circle_category(env: Env, creator: Address, circle_id: u64, category: CircleCategory) -> Result<(), SoroSusuError>;
+    fn get_circle_category(env: Env, circle_id: u64) -> Result<CircleCategory, SoroSusuError>;
+
+    // Up to `limit` ids (at most 50) of circles in a category, starting at position `start` of that list
+    fn get_circles_by_category(env: Env, category: CircleCategory, start: u64, limit: u64) -> Result<Vec<u64>, SoroSusuError>;
 
     // Make a deposit (Pay your weekly/monthly due)
-    fn deposit(env: Env, user: Address, circle_id: u64);
+    fn deposit(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Same as deposit, plus an event carrying a short payment reference
+    fn deposit_with_note(env: Env, user: Address, circle_id: u64, note: Symbol) -> Result<(), SoroSusuError>;
+
+    // Preview what deposit would charge right now: (base_amount, insurance_fee, late_penalty)
+    fn simulate_deposit(env: Env, user: Address, circle_id: u64) -> Result<(u64, u64, u64), SoroSusuError>;
+
+    // Pay another member's contribution; only the payer signs and the beneficiary is credited
+    fn deposit_on_behalf(env: Env, payer: Address, beneficiary: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Pay several members' dues in one call; reverts entirely if any deposit fails
+    fn bulk_deposit(env: Env, payer: Address, circle_id: u64, members: Vec<Address>) -> Result<(), SoroSusuError>;
 
-    // Move idle pot funds to the lending pool.
-    fn deposit_to_yield_pool(env: Env, caller: Address, circle_id: u64, amount: u64);
+    // Persistent 0-100 reliability score of a member
+    fn get_trust_score(env: Env, member: Address) -> Result<u32, SoroSusuError>;
+
+    // Timestamps of a member's last deposits (up to 12, oldest first)
+    fn get_contribution_history(env: Env, member: Address) -> Result<Vec<u64>, SoroSusuError>;
+
+    // Total tokens deposited across every circle, for protocol analytics. There is no matching fee total:
+    // claim_pot pays out the whole pot, and a fee counter belongs with the fee once payouts take one.
+    fn get_protocol_volume(env: Env) -> Result<u128, SoroSusuError>;
+
+    // Split this circle's late-fee reserve equally among on-time payers of the last finalized round (creator or admin)
+    fn distribute_group_reserve(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Supply this circle's idle funds to `pool` instead of the global lending pool (creator only; admin-approved pools)
+    fn set_circle_yield_strategy(env: Env, creator: Address, circle_id: u64, pool: Address) -> Result<(), SoroSusuError>;
+
+    // Move idle pot funds to the lending pool; at most what the circle holds for its pots
+    fn deposit_to_yield_pool(env: Env, caller: Address, circle_id: u64, amount: u64) -> Result<(), SoroSusuError>;
 
     // Withdraw all supplied idle funds back to the contract for payouts.
-    fn prepare_payout_liquidity(env: Env, caller: Address, circle_id: u64);
+    fn prepare_payout_liquidity(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Interest earned from the lending pool in the current round
+    fn get_yield_earnings(env: Env, circle_id: u64) -> Result<u64, SoroSusuError>;
+
+    // Whether the circle has funds supplied to the lending pool that have not been recalled yet
+    fn is_yield_active(env: Env, circle_id: u64) -> Result<bool, SoroSusuError>;
+
+    // Insurance balance, whether it was used this cycle, and the fee in bps
+    fn get_insurance_status(env: Env, circle_id: u64) -> Result<(u64, bool, u32), SoroSusuError>;
+
+    // Balance of the circle's insurance pool
+    fn get_insurance_fund(env: Env, circle_id: u64) -> Result<u64, SoroSusuError>;
 
     // Trigger insurance to cover a default
-    fn trigger_insurance_coverage(env: Env, caller: Address, circle_id: u64, member: Address);
+    fn trigger_insurance_coverage(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError>;
+
+    // Whether covering `member` would succeed right now, with a reason such as "ok" or "already_used"
+    fn can_trigger_insurance(env: Env, circle_id: u64, member: Address) -> Result<(bool, Symbol), SoroSusuError>;
+
+    // The circle's last 100 insurance payouts as (timestamp, beneficiary, amount), oldest first
+    fn get_insurance_claim_history(env: Env, circle_id: u64) -> Result<Vec<(u64, Address, u64)>, SoroSusuError>;
 
     // Propose a change to the late fee penalty
-    fn propose_penalty_change(env: Env, user: Address, circle_id: u64, new_bps: u32);
+    fn propose_penalty_change(env: Env, user: Address, circle_id: u64, new_bps: u32) -> Result<(), SoroSusuError>;
+
+    // Propose a change to the round duration (takes effect after the protocol's notice period, 72 hours by default)
+    fn propose_duration_change(env: Env, user: Address, circle_id: u64, new_duration: u64) -> Result<(), SoroSusuError>;
 
-    // Propose a change to the round duration (takes effect after 72 hours)
-    fn propose_duration_change(env: Env, user: Address, circle_id: u64, new_duration: u64);
+    // Schedule a new contribution amount without a vote (creator or admin; same notice as duration changes)
+    fn set_contribution_amount(env: Env, caller: Address, circle_id: u64, new_amount: u64) -> Result<(), SoroSusuError>;
+
+    // Withdraw a duration change that has not taken effect yet (creator or admin)
+    fn cancel_duration_change(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError>;
 
     // Vote on the current proposal
-    fn vote_penalty_change(env: Env, user: Address, circle_id: u64);
+    fn vote_penalty_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Override the late fee without a vote (creator or protocol admin)
+    fn admin_set_late_fee_bps(env: Env, caller: Address, circle_id: u64, new_bps: u32) -> Result<(), SoroSusuError>;
+
+    // The circle's last 50 late fee changes, voted or overridden, as (new_bps, timestamp)
+    fn get_penalty_history(env: Env, circle_id: u64) -> Result<Vec<(u32, u64)>, SoroSusuError>;
+
+    // The circle's last 100 resolved late fee and insurance fee proposals
+    fn get_governance_history(env: Env, circle_id: u64) -> Result<Vec<VoterTurnout>, SoroSusuError>;
+
+    // Propose and vote on a change to the contribution amount; a passed change applies when the round is finalized
+    fn propose_contribution_change(env: Env, user: Address, circle_id: u64, new_amount: u64) -> Result<(), SoroSusuError>;
+    fn vote_contribution_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Propose and vote on a new insurance fee; a passed change applies from the next round's deposits
+    fn propose_insurance_fee_change(env: Env, user: Address, circle_id: u64, new_bps: u32) -> Result<(), SoroSusuError>;
+    fn vote_insurance_fee_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Propose and vote on pushing this round's deadline back by up to 7 days, once per round
+    fn propose_deadline_extension(env: Env, user: Address, circle_id: u64, extension_secs: u64) -> Result<(), SoroSusuError>;
+    fn vote_deadline_extension(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Propose and vote on growing or shrinking the circle
+    fn propose_max_members_change(env: Env, user: Address, circle_id: u64, new_max: u32) -> Result<(), SoroSusuError>;
+    fn vote_max_members_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
 
     // Propose and vote for social recovery address change
-    fn propose_address_change(env: Env, user: Address, circle_id: u64, old_address: Address, new_address: Address);
-    fn vote_for_recovery(env: Env, user: Address, circle_id: u64);
+    fn propose_address_change(env: Env, user: Address, circle_id: u64, old_address: Address, new_address: Address) -> Result<(), SoroSusuError>;
+    fn vote_for_recovery(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Clear a recovery proposal that has stopped accepting votes; anyone may call it
+    fn cancel_expired_recovery(env: Env, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Name someone who may propose moving your seat to a new key if yours is lost
+    fn set_emergency_contact(env: Env, member: Address, contact: Address) -> Result<(), SoroSusuError>;
+    fn get_emergency_contact(env: Env, member: Address) -> Result<Option<Address>, SoroSusuError>;
+
+    // Move your own seat and membership NFT to a new key; both keys sign, so no vote is needed
+    fn reclaim_nft(env: Env, old_address: Address, new_address: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Choose whether ejected members get their base contributions back (creator only)
+    fn set_refund_ejected_members(env: Env, caller: Address, circle_id: u64, enabled: bool) -> Result<(), SoroSusuError>;
+
+    // Share of members, in basis points (5001 to 10000), needed to pass fee and recovery votes.
+    // Creator only, and only before anyone has joined
+    fn set_quorum_threshold(env: Env, caller: Address, circle_id: u64, quorum_threshold_bps: u32) -> Result<(), SoroSusuError>;
+
+    // How long before each deadline deposits pull the circle's funds out of the lending pool:
+    // at least the protocol's buffer and at most one cycle (creator only)
+    fn update_yield_idle_threshold(env: Env, creator: Address, circle_id: u64, new_secs: u64) -> Result<(), SoroSusuError>;
+
+    // Choose between join-order and lowest-trust-score-first payouts (creator only)
+    fn set_priority_ordering(env: Env, caller: Address, circle_id: u64, enabled: bool) -> Result<(), SoroSusuError>;
+
+    // Point the circle at an upgraded NFT contract for future mints and burns (creator only)
+    fn set_nft_contract(env: Env, caller: Address, circle_id: u64, new_nft_contract: Address) -> Result<(), SoroSusuError>;
+
+    // Tokens members may deposit with; must include the payout token (creator only)
+    fn set_accepted_tokens(env: Env, caller: Address, circle_id: u64, tokens: Vec<Address>) -> Result<(), SoroSusuError>;
+
+    // Choose which accepted token this member's contributions are paid in
+    fn set_deposit_token(env: Env, user: Address, circle_id: u64, token: Address) -> Result<(), SoroSusuError>;
+
+    // Members still waiting for the pot, in the order they are due to receive it
+    fn get_payout_order(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError>;
+
+    // Members who have and have not contributed in the current round, in seat order
+    fn get_round_contributors(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError>;
+    fn get_round_defaulters(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError>;
+
+    // Every seat's (address, amount paid this round, has contributed), in seat order
+    fn get_round_contribution_summary(env: Env, circle_id: u64) -> Result<Vec<(Address, u64, bool)>, SoroSusuError>;
+
+    // Members who have exited and whose seats are waiting to be filled, in seat order
+    fn get_pending_exits(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError>;
+
+    // How many rounds of the circle have been finalized
+    fn get_round_number(env: Env, circle_id: u64) -> Result<u32, SoroSusuError>;
+
+    // Lifetime totals for the circle: rounds, volume, late payments, insurance use, joins and ejections
+    fn get_circle_stats(env: Env, circle_id: u64) -> Result<CircleStats, SoroSusuError>;
+
+    // Seconds since the circle was created
+    fn get_circle_age(env: Env, circle_id: u64) -> Result<u64, SoroSusuError>;
+
+    // When the circle's last round was finalized; None before the first one
+    fn get_last_finalized_at(env: Env, circle_id: u64) -> Result<Option<u64>, SoroSusuError>;
+
+    // Temporarily suspend a member and lift the suspension (creator only)
+    fn freeze_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError>;
+    fn unfreeze_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError>;
 
     // Eject a member (burns NFT)
-    fn eject_member(env: Env, caller: Address, circle_id: u64, member: Address);
+    fn eject_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError>;
+
+    // Drop ejected members' seats and renumber the rest so seats stay contiguous (creator only)
+    fn sweep_inactive_members(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError>;
     
     // Request graceful exit from the circle
-    fn request_exit(env: Env, user: Address, circle_id: u64);
-    fn fill_vacancy(env: Env, new_member: Address, circle_id: u64, exiting_member_address: Address);
+    fn request_exit(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+    fn cancel_exit_request(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+    fn fill_vacancy(env: Env, new_member: Address, circle_id: u64, exiting_member_address: Address) -> Result<(), SoroSusuError>;
+
+    // Ask to move to another tier; once the creator approves, it applies from the next round
+    fn request_tier_change(env: Env, user: Address, circle_id: u64, new_tier: u32) -> Result<(), SoroSusuError>;
+    fn approve_tier_change(env: Env, creator: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError>;
+
+    // Remove an ejected member's entries once the purge delay has passed
+    fn purge_exited_member(env: Env, member: Address) -> Result<(), SoroSusuError>;
+
+    // Look up which circle a member belongs to
+    fn get_member_circle(env: Env, member: Address) -> Result<u64, SoroSusuError>;
+    fn get_member_join_time(env: Env, member: Address) -> Result<u64, SoroSusuError>;
+
+    // Milestone badges an address has earned, in the order they were awarded
+    fn get_member_badges(env: Env, member: Address) -> Vec<BadgeType>;
+
+    // Who referred a member when they joined, and how many members an address has referred
+    fn get_referrer(env: Env, member: Address) -> Result<Option<Address>, SoroSusuError>;
+    fn get_referral_count(env: Env, referrer: Address) -> Result<u32, SoroSusuError>;
+    // Seated members by status as (active, awaiting_replacement, ejected, frozen)
+    fn get_member_status_counts(env: Env, circle_id: u64) -> Result<(u32, u32, u32, u32), SoroSusuError>;
+
+    // Whether every seat in the circle is taken
+    fn is_circle_full(env: Env, circle_id: u64) -> Result<bool, SoroSusuError>;
+
+    // Seats taken in a circle, read without loading the whole circle
+    fn get_member_count(env: Env, circle_id: u64) -> Result<u32, SoroSusuError>;
+
+    // Everyone who has ever taken a seat in the circle, replacements included, in the order they joined
+    fn get_join_order(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError>;
+
+    // Keep the contract's storage from expiring; any active member of the circle may call it
+    fn refresh_ttl(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Configure the multi-sig admin set (protocol admin only)
+    fn set_admin_list(env: Env, admin: Address, admins: Vec<Address>, threshold: u32) -> Result<(), SoroSusuError>;
+
+    // Pay the finalized round's pot to its recipient once the scheduled payout time is reached
+    fn claim_pot(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Preview the finalized pot as (gross_pot, net_after_fee); (0, 0) before finalization
+    fn simulate_payout(env: Env, circle_id: u64) -> Result<(u64, u64), SoroSusuError>;
+
+    // Abort the open round and hand every contribution back; fees and penalties are kept (creator or admin)
+    fn cancel_round(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Close a circle once every member has been paid, returning leftover insurance to members (creator only)
+    fn close_circle(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError>;
+
+    // Propose multi-sig operations; the proposer's approval is counted immediately
+    fn propose_eject_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<u64, SoroSusuError>;
+    fn propose_finalize_round(env: Env, caller: Address, circle_id: u64) -> Result<u64, SoroSusuError>;
+    fn propose_trigger_insurance(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<u64, SoroSusuError>;
+
+    // Clear yield accounting when the lending pool cannot return funds (multi-sig; always at least two admins)
+    fn admin_zero_yield_deposited(env: Env, caller: Address, circle_id: u64) -> Result<u64, SoroSusuError>;
+
+    // Approve a pending operation; executes once the threshold is met
+    fn approve_operation(env: Env, admin: Address, operation_id: u64, nonce: u64) -> Result<(), SoroSusuError>;
+
+    // Approve several operations under one nonce; missing, executed or already signed ids are skipped
+    fn batch_approve_operations(env: Env, admin: Address, operation_ids: Vec<u64>, nonce: u64) -> Result<(), SoroSusuError>;
+
+    // Nonce the admin's next approval must carry
+    fn get_admin_nonce(env: Env, admin: Address) -> u64;
+
+    // Remove an operation that expired before reaching the threshold
+    fn cancel_expired_operation(env: Env, operation_id: u64) -> Result<(), SoroSusuError>;
+
+    // Inspect multi-sig operations
+    fn get_operation(env: Env, operation_id: u64) -> Result<AdminOperation, SoroSusuError>;
+    fn get_pending_operations(env: Env, circle_id: u64) -> Result<Vec<AdminOperation>, SoroSusuError>;
+
+    // Admins who have signed an operation, proposer first, and how many more signatures it needs
+    fn get_operation_approvers(env: Env, operation_id: u64) -> Result<Vec<Address>, SoroSusuError>;
+    fn get_remaining_approvals_needed(env: Env, operation_id: u64) -> Result<u32, SoroSusuError>;
+}
+
+fn require_multisig_admin(env: &Env, admin: &Address) -> Result<(), SoroSusuError> {
+    let admins: Vec<Address> = env.storage().instance().get(&DataKey::AdminList).unwrap_or_else(|| Vec::new(env));
+    if !admins.contains(admin) {
+        return Err(SoroSusuError::Unauthorized);
+    }
+    Ok(())
+}
+
+// The nonce is part of the signed arguments, so a captured approval cannot be submitted again
+fn consume_admin_nonce(env: &Env, admin: &Address, nonce: u64) -> Result<(), SoroSusuError> {
+    let nonce_key = DataKey::OperationNonce(admin.clone());
+    let expected_nonce: u64 = env.storage().instance().get(&nonce_key).unwrap_or(0);
+    if nonce != expected_nonce {
+        return Err(SoroSusuError::InvalidOperation);
+    }
+    env.storage().instance().set(&nonce_key, &(expected_nonce + 1));
+    Ok(())
+}
+
+// Add an admin's approval to a live operation and execute it if that meets the threshold
+fn approve_live_operation(env: &Env, admin: Address, operation: &mut AdminOperation) -> Result<(), SoroSusuError> {
+    if env.ledger().timestamp() > operation.expiry_timestamp {
+        return Err(SoroSusuError::OperationExpired);
+    }
+    // Proposing counts as approving, so this also stops a proposer from signing twice
+    if operation.approvals.contains(&admin) {
+        return Err(SoroSusuError::InvalidOperation);
+    }
+
+    operation.approvals.push_back(admin);
+    try_execute_operation(env, operation)
+}
+
+// Approvals an operation needs: the admin threshold, raised for writing off yield
+fn approval_threshold(env: &Env, operation: &AdminOperation) -> u32 {
+    let threshold: u32 = env.storage().instance().get(&DataKey::ApprovalThreshold).unwrap_or(1);
+    if operation.operation_type == 4 {
+        return threshold.max(MIN_ZERO_YIELD_APPROVALS);
+    }
+    threshold
+}
+
+// Record approval progress and execute the operation once the threshold is met
+fn try_execute_operation(env: &Env, operation: &mut AdminOperation) -> Result<(), SoroSusuError> {
+    let threshold = approval_threshold(env, operation);
+    if operation.approvals.len() >= threshold {
+        execute_operation(env, operation)?;
+        operation.is_executed = true;
+
+        let pending_key = DataKey::PendingOperationCount(operation.circle_id);
+        let pending: u32 = env.storage().instance().get(&pending_key).unwrap_or(0);
+        env.storage().instance().set(&pending_key, &pending.saturating_sub(1));
+    }
+    env.storage().instance().set(&DataKey::Operation(operation.id), operation);
+    Ok(())
+}
+
+fn create_operation(env: &Env, caller: Address, circle_id: u64, operation_type: u32, target_member: Option<Address>) -> Result<u64, SoroSusuError> {
+    caller.require_auth();
+    require_multisig_admin(env, &caller)?;
+
+    if !env.storage().instance().has(&DataKey::Circle(circle_id)) {
+        return Err(SoroSusuError::CircleNotFound);
+    }
+
+    let operation_id: u64 = env.storage().instance().get(&DataKey::OperationCount).unwrap_or(0) + 1;
+    env.storage().instance().set(&DataKey::OperationCount, &operation_id);
+
+    let mut approvals = Vec::new(env);
+    approvals.push_back(caller.clone());
+    let created_at = env.ledger().timestamp();
+    let mut operation = AdminOperation {
+        id: operation_id,
+        operation_type,
+        caller,
+        target_member,
+        circle_id,
+        approvals,
+        created_at,
+        expiry_timestamp: created_at + OPERATION_EXPIRY_SECS,
+        is_executed: false,
+    };
+
+    let ids_key = DataKey::CircleOperations(circle_id);
+    let mut ids: Vec<u64> = env.storage().instance().get(&ids_key).unwrap_or_else(|| Vec::new(env));
+    ids.push_back(operation_id);
+    env.storage().instance().set(&ids_key, &ids);
+
+    let pending_key = DataKey::PendingOperationCount(circle_id);
+    let pending: u32 = env.storage().instance().get(&pending_key).unwrap_or(0);
+    env.storage().instance().set(&pending_key, &(pending + 1));
+
+    try_execute_operation(env, &mut operation)?;
+    Ok(operation_id)
 }
 
 // Execute an admin operation when threshold is met
-fn execute_operation(env: &Env, operation: &AdminOperation) {
+fn execute_operation(env: &Env, operation: &AdminOperation) -> Result<(), SoroSusuError> {
     match operation.operation_type {
         1 => execute_eject_member(env, operation),
         2 => execute_finalize_round(env, operation),
         3 => execute_trigger_insurance(env, operation),
-        _ => panic!("Invalid operation type"),
+        4 => execute_zero_yield_deposited(env, operation),
+        _ => Err(SoroSusuError::InvalidOperation),
     }
 }
 
+// Write off yield that the lending pool failed to return, without calling the pool
+fn execute_zero_yield_deposited(env: &Env, operation: &AdminOperation) -> Result<(), SoroSusuError> {
+    let circle_key = DataKey::Circle(operation.circle_id);
+    let mut circle: CircleInfo = env.storage().instance()
+        .get(&circle_key)
+        .ok_or(SoroSusuError::CircleNotFound)?;
+
+    let zeroed_amount = circle.yield_deposited;
+    circle.yield_deposited = 0;
+    env.storage().instance().set(&circle_key, &circle);
+
+    env.events().publish(
+        (Symbol::new(env, "YIELD_RECOVERY_OVERRIDE"), operation.circle_id),
+        zeroed_amount,
+    );
+    Ok(())
+}
+
 // Execute eject member operation
-fn execute_eject_member(env: &Env, operation: &AdminOperation) {
+fn execute_eject_member(env: &Env, operation: &AdminOperation) -> Result<(), SoroSusuError> {
     let circle_id = operation.circle_id;
     let target_member = operation
         .target_member
         .clone()
-        .unwrap_or_else(|| panic!("No target member"));
+        .ok_or(SoroSusuError::InvalidOperation)?;
     
-    let circle: CircleInfo = env.storage().instance()
+    let mut circle: CircleInfo = env.storage().instance()
         .get(&DataKey::Circle(circle_id))
-        .unwrap_or_else(|| panic!("Circle not found"));
-    
+        .ok_or(SoroSusuError::CircleNotFound)?;
+
+    let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(target_member.clone()));
+    if member_circle != Some(circle_id) {
+        return Err(SoroSusuError::NotAMember);
+    }
     let member_key = DataKey::Member(target_member.clone());
     let mut member_info: Member = env.storage().instance()
         .get(&member_key)
-        .unwrap_or_else(|| panic!("Member not found"));
+        .ok_or(SoroSusuError::NotAMember)?;
 
-    if !member_info.is_active {
-        panic!("Member already ejected");
+    if member_info.status != MemberStatus::Active {
+        return Err(SoroSusuError::MemberNotActive);
     }
 
-    // Mark as inactive
-    member_info.is_active = false;
+    refund_ejected_member(env, &mut circle, &member_info);
+    env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+    // Mark as ejected
+    member_info.status = MemberStatus::Ejected;
+    update_circle_stats(env, circle_id, |stats| stats.total_members_ejected += 1);
     env.storage().instance().set(&member_key, &member_info);
+    env.storage().instance().set(&DataKey::EjectionTime(target_member.clone()), &env.ledger().timestamp());
 
     // Burn NFT
-    let token_id = (circle_id as u128) << 64 | (member_info.index as u128);
+    let token_id = encode_nft_token_id(circle_id, member_info.index);
     let client = SusuNftClient::new(env, &circle.nft_contract);
     client.burn(&target_member, &token_id);
+    Ok(())
 }
 
-// Get member address by index from storage
-fn get_member_address_by_index(env: &Env, circle_id: u64, index: u32) -> Address {
-    let circle: CircleInfo = env.storage().instance()
-        .get(&DataKey::Circle(circle_id))
-        .unwrap_or_else(|| panic!("Circle not found"));
-    
-    if index >= circle.member_count {
-        panic!("Member index out of bounds");
+// Take a seat's deposit back out of the open round; returns what the member paid in. Insurance cover
+// goes back to the insurance pool instead, since the member never paid it.
+fn withdraw_round_contribution(env: &Env, circle: &mut CircleInfo, member: &Member) -> u64 {
+    if (circle.contribution_bitmap >> member.index) & 1 == 0 {
+        return 0;
     }
-    
-    circle.member_addresses.get(index).unwrap().clone()
+
+    let contribution_key = DataKey::CurrentRoundContribution(circle.id, member.index);
+    let amount: u64 = env.storage().instance().get(&contribution_key).unwrap_or(0);
+    env.storage().instance().remove(&contribution_key);
+    circle.contribution_bitmap &= !(1 << member.index);
+    circle.late_contribution_bitmap &= !(1 << member.index);
+
+    let deposit_key = DataKey::Deposit(circle.id, member.address.clone());
+    if !env.storage().instance().has(&deposit_key) {
+        let balance = insurance_fund(env, circle.id);
+        env.storage().instance().set(&DataKey::InsuranceFund(circle.id), &(balance + amount));
+        circle.is_insurance_used = false;
+        return 0;
+    }
+    env.storage().instance().remove(&deposit_key);
+    amount
 }
 
-fn has_successful_referral(env: &Env, circle: &CircleInfo, candidate_referrer: &Address) -> bool {
-    let member_count = circle.member_count as u32;
-    for i in 0..member_count {
-        let member_address = circle.member_addresses.get(i).unwrap();
-        let member_key = DataKey::Member(member_address);
-        let referred_member: Member = match env.storage().instance().get(&member_key) {
-            Some(member) => member,
-            None => continue,
-        };
+// Return a departing member's base contributions; the caller saves the circle.
+// Earlier rounds' contributions went out in those rounds' pots, so the refund can only come from what
+// this circle still holds: the member's deposit for the open round, then a pot not yet claimed.
+// Pays what is there if short.
+fn refund_contributions(env: &Env, circle: &mut CircleInfo, member: &Member) {
+    // Whoever has had their pot already got back more than they paid in
+    if (circle.payout_bitmap >> member.index) & 1 == 1 {
+        return;
+    }
 
-        if referred_member.referrer == Some(candidate_referrer.clone()) {
-            return true;
-        }
+    let refund_amount = member.total_contributed;
+    if refund_amount == 0 {
+        return;
     }
-    false
-}
 
-fn apply_referral_discount(env: &Env, circle: &CircleInfo, member: &Member, penalty_amount: u64) -> u64 {
-    if penalty_amount == 0 {
-        return 0;
+    let round_deposit = withdraw_round_contribution(env, circle, member);
+    let paid_amount = core::cmp::min(refund_amount, round_deposit + unclaimed_pot(env, circle));
+    let from_pot = paid_amount.saturating_sub(round_deposit);
+    if from_pot > 0 {
+        let round_pot: u64 = env.storage().instance().get(&DataKey::RoundPot(circle.id)).unwrap_or(0);
+        env.storage().instance().set(&DataKey::RoundPot(circle.id), &(round_pot - from_pot));
     }
 
-    if has_successful_referral(env, circle, &member.address) {
-        let discount = (penalty_amount * REFERRAL_DISCOUNT_BPS as u64) / 10000;
-        penalty_amount.saturating_sub(discount)
-    } else {
-        penalty_amount
+    if paid_amount > 0 {
+        let token_client = token::Client::new(env, &circle.token);
+        token_client.transfer(&env.current_contract_address(), &member.address, &(paid_amount as i128));
+    }
+
+    if paid_amount < refund_amount {
+        env.events().publish(
+            (Symbol::new(env, "PARTIAL_REFUND"), circle.id),
+            (member.address.clone(), paid_amount, refund_amount),
+        );
     }
 }
 
-fn count_active_members(env: &Env, circle: &CircleInfo) -> u32 {
-    let mut active_count = 0u32;
-    for i in 0..circle.member_count {
-        let member_address = circle.member_addresses.get(i).unwrap();
-        let member_key = DataKey::Member(member_address);
-        if let Some(member) = env.storage().instance().get::<DataKey, Member>(&member_key) {
-            if member.status == MemberStatus::Active {
-                active_count += 1;
-            }
-        }
+// Refund an ejected member only when the circle opts in; the caller saves the circle
+fn refund_ejected_member(env: &Env, circle: &mut CircleInfo, member: &Member) {
+    if circle.refund_ejected_members {
+        refund_contributions(env, circle, member);
     }
-    active_count
 }
 
-fn apply_recovery_if_consensus(env: &Env, circle: &mut CircleInfo) {
-    let active_members = count_active_members(env, circle);
-    if active_members == 0 {
-        panic!("No active members");
+// Seat a new member in the circle and mint their participation NFT; the caller saves the circle
+fn add_member(env: &Env, circle: &mut CircleInfo, user: &Address, tier_multiplier: u32, referrer: Option<Address>, require_invite: bool) -> Result<(), SoroSusuError> {
+    if is_blocked(env, user) {
+        return Err(SoroSusuError::AddressBlocked);
     }
 
-    let votes = circle.recovery_votes_bitmap.count_ones();
-    if votes * 100 <= active_members * 70 {
-        return;
+    if !circle.is_active {
+        return Err(SoroSusuError::CircleClosed);
     }
 
-    let old_address = circle
-        .recovery_old_address
-        .clone()
-        .unwrap_or_else(|| panic!("No recovery proposal"));
-    let new_address = circle
-        .recovery_new_address
-        .clone()
-        .unwrap_or_else(|| panic!("No recovery proposal"));
+    // Check if the circle is full
+    if circle.member_count >= circle.max_members {
+        return Err(SoroSusuError::CircleFull);
+    }
 
-    let old_member_key = DataKey::Member(old_address.clone());
-    let mut old_member: Member = env
-        .storage()
-        .instance()
-        .get(&old_member_key)
-        .unwrap_or_else(|| panic!("Old member not found"));
+    // Member indices address bits of the u64 bitmaps, so there is no seat 64 regardless of max_members
+    if circle.member_count >= 64 {
+        return Err(SoroSusuError::MaxMembersExceeded);
+    }
 
-    if old_member.status != MemberStatus::Active {
-        panic!("Only active members can be recovered");
+    // Any entry at all, including an exited one not yet purged, means the address is taken
+    let member_key = DataKey::Member(user.clone());
+    if env.storage().instance().has(&member_key) {
+        return Err(SoroSusuError::AlreadyMember);
     }
 
-    let new_member_key = DataKey::Member(new_address.clone());
-    if env.storage().instance().has(&new_member_key) {
-        panic!("New address is already a member");
+    // Invite-only circles consume the invitee's single-use invite
+    let invite_key = DataKey::Invite(circle.id, user.clone());
+    if require_invite && circle.is_invite_only {
+        if !env.storage().instance().has(&invite_key) {
+            return Err(SoroSusuError::InviteRequired);
+        }
+        env.storage().instance().remove(&invite_key);
     }
 
-    old_member.address = new_address.clone();
-    env.storage().instance().set(&new_member_key, &old_member);
-    env.storage().instance().remove(&old_member_key);
+    // Validate tier_multiplier (must be at least 1)
+    if tier_multiplier == 0 {
+        return Err(SoroSusuError::InvalidTier);
+    }
 
-    let mut updated_addresses = Vec::new(env);
-    for i in 0..circle.member_count {
-        let member_address = circle.member_addresses.get(i).unwrap();
-        if i == old_member.index {
-            updated_addresses.push_back(new_address.clone());
-        } else {
-            updated_addresses.push_back(member_address);
+    if let Some(referrer_address) = referrer.clone() {
+        if referrer_address == *user {
+            return Err(SoroSusuError::InvalidReferrer);
+        }
+        // Referral discounts are earned inside one circle, so the referrer must already sit in it
+        let referrer_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(referrer_address.clone()));
+        if referrer_circle != Some(circle.id) {
+            return Err(SoroSusuError::InvalidReferrer);
         }
-    }
-    circle.member_addresses = updated_addresses;
 
-    circle.recovery_old_address = None;
-    circle.recovery_new_address = None;
-    circle.recovery_votes_bitmap = 0;
-}
+        let count_key = MetadataKey::ReferralCount(referrer_address);
+        let count: u32 = env.storage().instance().get(&count_key).unwrap_or(0);
+        env.storage().instance().set(&count_key, &(count + 1));
+    }
 
-// Execute finalize round operation
-fn execute_finalize_round(env: &Env, operation: &AdminOperation) {
-    let circle_id = operation.circle_id;
-    let mut circle: CircleInfo = env.storage().instance()
-        .get(&DataKey::Circle(circle_id))
-        .unwrap_or_else(|| panic!("Circle not found"));
+    // Create and store the new member
+    let new_member = Member {
+        address: user.clone(),
+        index: circle.member_count,
+        contribution_count: 0,
+        last_contribution_time: 0,
+        tier_multiplier,
+        status: MemberStatus::Active,
+        total_contributed: 0,
+        referrer,
+        trust_score: 0,
+        frozen_rounds: 0,
+        emergency_contact: None,
+        late_contributions: 0,
+    };
+
+    env.storage().instance().set(&member_key, &new_member);
+    env.storage().instance().set(&DataKey::MemberCircle(user.clone()), &circle.id);
+    env.storage().instance().set(&DataKey::MemberJoinTime(user.clone()), &env.ledger().timestamp());
+    circle.member_addresses.push_back(user.clone());
+    circle.member_count += 1;
+    env.storage().instance().set(&DataKey::CircleMemberCount(circle.id), &circle.member_count);
+    update_circle_stats(env, circle.id, |stats| stats.total_members_joined += 1);
+    record_join(env, circle.id, user);
+
+    // Mint Participation NFT
+    let token_id = encode_nft_token_id(circle.id, new_member.index);
+    let client = SusuNftClient::new(env, &circle.nft_contract);
+    client.mint(user, &token_id);
 
-    // Check if round is already finalized
-    if circle.is_round_finalized {
-        panic!("Round is already finalized");
+    if circle.member_count == circle.max_members {
+        env.events().publish((Symbol::new(env, "CIRCLE_FULL"), circle.id), circle.member_count);
     }
+    Ok(())
+}
 
-    // Check if all members have contributed (all bits set in contribution_bitmap)
-    let expected_bitmap = (1u64 << circle.member_count) - 1;
-    if circle.contribution_bitmap != expected_bitmap {
-        panic!("Not all members have contributed");
+// Seated members whose contribution bit for this round matches `contributed`
+fn members_by_contribution(env: &Env, circle: &CircleInfo, contributed: bool) -> Vec<Address> {
+    let mut members = Vec::new(env);
+    for index in 0..circle.member_count {
+        if ((circle.contribution_bitmap >> index) & 1 == 1) != contributed {
+            continue;
+        }
+        if let Some(address) = get_member_address_by_index(circle, index) {
+            members.push_back(address);
+        }
     }
+    members
+}
 
-    // Set scheduled payout time (24 hours from now)
-    let current_time = env.ledger().timestamp();
-    let scheduled_payout_time = current_time + 86400; // 24 hours in seconds
+fn circle_settings(env: &Env, circle_id: u64) -> CircleSettings {
+    env.storage().instance().get(&DataKey::CircleSettings(circle_id)).unwrap_or(CircleSettings {
+        pending_contribution_amount: 0,
+        contribution_effective_at: 0,
+        quorum_threshold_bps: MIN_QUORUM_BPS,
+        created_at: 0,
+        max_yield_idle_secs: 0,
+    })
+}
+
+fn protocol_config(env: &Env) -> ProtocolConfig {
+    env.storage().instance().get(&DataKey::ProtocolConfig).unwrap_or(ProtocolConfig {
+        yield_liquidity_buffer_secs: DEFAULT_YIELD_LIQUIDITY_BUFFER_SECS,
+        duration_change_notice_secs: DEFAULT_DURATION_CHANGE_NOTICE_SECS,
+        min_cycle_duration_secs: DEFAULT_MIN_CYCLE_DURATION_SECS,
+    })
+}
+
+// New circles get one full cycle to settle in before fee and duration changes can be proposed
+fn require_governance_age(env: &Env, circle: &CircleInfo) -> Result<(), SoroSusuError> {
+    let created_at = circle_settings(env, circle.id).created_at;
+    if env.ledger().timestamp().saturating_sub(created_at) < circle.cycle_duration {
+        return Err(SoroSusuError::InvalidOperation);
+    }
+    Ok(())
+}
+
+// Whether `votes` out of `voters` meets the circle's quorum, or `floor_bps` if that is stricter
+fn reaches_quorum(env: &Env, circle_id: u64, votes: u32, voters: u32, floor_bps: u32) -> bool {
+    let quorum_bps = circle_settings(env, circle_id).quorum_threshold_bps.max(floor_bps);
+    votes as u64 * 10000 >= quorum_bps as u64 * voters as u64
+}
+
+// Move a circle into a category's index, taking it out of the one it was in before
+fn tag_circle_category(env: &Env, circle_id: u64, category: CircleCategory) {
+    let category_key = MetadataKey::CircleCategory(circle_id);
+    let previous: Option<CircleCategory> = env.storage().instance().get(&category_key);
+    if previous == Some(category.clone()) {
+        return;
+    }
+
+    if let Some(previous) = previous {
+        let old_index_key = MetadataKey::CategoryIndex(previous);
+        if let Some(mut ids) = env.storage().instance().get::<MetadataKey, Vec<u64>>(&old_index_key) {
+            if let Some(position) = ids.first_index_of(circle_id) {
+                ids.remove(position);
+                env.storage().instance().set(&old_index_key, &ids);
+            }
+        }
+    }
+
+    let index_key = MetadataKey::CategoryIndex(category.clone());
+    let mut ids: Vec<u64> = env.storage().instance().get(&index_key).unwrap_or_else(|| Vec::new(env));
+    ids.push_back(circle_id);
+    env.storage().instance().set(&index_key, &ids);
+    env.storage().instance().set(&category_key, &category);
+}
+
+// Switch to a scheduled contribution amount once its notice period has passed
+fn apply_due_contribution_change(env: &Env, circle: &mut CircleInfo, current_time: u64) {
+    let mut settings = circle_settings(env, circle.id);
+    if settings.pending_contribution_amount == 0 || current_time < settings.contribution_effective_at {
+        return;
+    }
+
+    circle.contribution_amount = settings.pending_contribution_amount;
+    settings.pending_contribution_amount = 0;
+    settings.contribution_effective_at = 0;
+    env.storage().instance().set(&DataKey::CircleSettings(circle.id), &settings);
+}
+
+// Token the member pays contributions in; falls back to the payout token if their choice is no longer accepted
+fn member_deposit_token(env: &Env, circle: &CircleInfo, member: &Address) -> Address {
+    match env.storage().instance().get::<DataKey, Address>(&DataKey::MemberToken(member.clone())) {
+        Some(token) if circle.accepted_tokens.contains(&token) => token,
+        _ => circle.token.clone(),
+    }
+}
+
+// For entry points that take a circle_id without otherwise loading the circle
+fn require_circle_exists(env: &Env, circle_id: u64) -> Result<(), SoroSusuError> {
+    if !env.storage().instance().has(&DataKey::Circle(circle_id)) {
+        return Err(SoroSusuError::CircleNotFound);
+    }
+    Ok(())
+}
+
+fn require_protocol_active(env: &Env) -> Result<(), SoroSusuError> {
+    if env.storage().instance().get(&DataKey::ProtocolPaused).unwrap_or(false) {
+        return Err(SoroSusuError::ProtocolPaused);
+    }
+    Ok(())
+}
+
+// Membership NFT id of a seat: the circle id in the high 64 bits, the seat index in the low 64.
+// Both halves are at least as wide as their inputs, so distinct seats never share an id.
+pub fn encode_nft_token_id(circle_id: u64, index: u32) -> u128 {
+    // Seat indices address bits of the u64 bitmaps
+    debug_assert!(index < 64);
+    (circle_id as u128) << 64 | index as u128
+}
+
+// Inverse of `encode_nft_token_id`: (circle_id, seat_index)
+pub fn decode_nft_token_id(token_id: u128) -> (u64, u32) {
+    ((token_id >> 64) as u64, token_id as u64 as u32)
+}
+
+// Whether the protocol admin has barred this address from all circles
+fn is_blocked(env: &Env, address: &Address) -> bool {
+    env.storage().instance().get(&DataKey::BlockedAddress(address.clone())).unwrap_or(false)
+}
+
+// Get member address by index, or None if no member holds that seat
+fn get_member_address_by_index(circle: &CircleInfo, index: u32) -> Option<Address> {
+    if index >= circle.member_count {
+        return None;
+    }
+
+    circle.member_addresses.get(index)
+}
+
+fn has_successful_referral(env: &Env, circle: &CircleInfo, candidate_referrer: &Address) -> bool {
+    for i in 0..circle.member_count {
+        let member_address = circle.member_addresses.get(i).unwrap();
+        let member_key = DataKey::Member(member_address);
+        let referred_member: Member = match env.storage().instance().get(&member_key) {
+            Some(member) => member,
+            None => continue,
+        };
+
+        if referred_member.referrer == Some(candidate_referrer.clone()) {
+            return true;
+        }
+    }
+    false
+}
+
+fn apply_referral_discount(env: &Env, circle: &CircleInfo, member: &Member, penalty_amount: u64) -> u64 {
+    if penalty_amount == 0 {
+        return 0;
+    }
+
+    if has_successful_referral(env, circle, &member.address) {
+        let discount = (penalty_amount * REFERRAL_DISCOUNT_BPS as u64) / 10000;
+        penalty_amount.saturating_sub(discount)
+    } else {
+        penalty_amount
+    }
+}
+
+// Contribution, insurance fee and late penalty a member owes when depositing at current_time
+fn deposit_cost(env: &Env, circle: &CircleInfo, member: &Member, current_time: u64) -> (u64, u64, u64) {
+    let member_contribution_amount = circle.contribution_amount * member.tier_multiplier as u64;
+    let insurance_fee = ((member_contribution_amount as u128 * circle.insurance_fee_bps as u128) / 10000) as u64;
+
+    let mut penalty_amount = 0u64;
+    if current_time > circle.deadline_timestamp + circle.grace_period_secs {
+        // Calculate penalty based on dynamic rate and member's tier
+        let base_penalty = (member_contribution_amount * circle.late_fee_bps as u64) / 10000;
+
+        // Stack one penalty per full cycle that has gone by since the last round closed
+        let missed_cycles = env.storage().instance()
+            .get::<DataKey, u64>(&DataKey::LastRoundFinalizedAt(circle.id))
+            .map(|finalized_at| current_time.saturating_sub(finalized_at) / circle.cycle_duration)
+            .unwrap_or(1)
+            .max(1);
+        penalty_amount = apply_referral_discount(env, circle, member, base_penalty.saturating_mul(missed_cycles));
+    }
+
+    (member_contribution_amount, insurance_fee, penalty_amount)
+}
+
+// Full pot for a round: every member's tier-adjusted contribution
+fn expected_round_pot(env: &Env, circle: &CircleInfo) -> u64 {
+    let mut pot = 0u64;
+    for i in 0..circle.member_count {
+        let member_address = circle.member_addresses.get(i).unwrap();
+        if let Some(member) = env.storage().instance().get::<DataKey, Member>(&DataKey::Member(member_address)) {
+            if member.status == MemberStatus::Active {
+                pot += circle.contribution_amount * member.tier_multiplier as u64;
+            }
+        }
+    }
+    pot
+}
+
+// Seated members by status as (active, awaiting_replacement, ejected, frozen)
+fn member_status_counts(env: &Env, circle: &CircleInfo) -> (u32, u32, u32, u32) {
+    let mut counts = (0u32, 0u32, 0u32, 0u32);
+    for i in 0..circle.member_count {
+        let member_address = circle.member_addresses.get(i).unwrap();
+        let member_key = DataKey::Member(member_address);
+        if let Some(member) = env.storage().instance().get::<DataKey, Member>(&member_key) {
+            match member.status {
+                MemberStatus::Active => counts.0 += 1,
+                MemberStatus::AwaitingReplacement => counts.1 += 1,
+                MemberStatus::Ejected => counts.2 += 1,
+                MemberStatus::Frozen => counts.3 += 1,
+            }
+        }
+    }
+    counts
+}
+
+fn count_active_members(env: &Env, circle: &CircleInfo) -> u32 {
+    member_status_counts(env, circle).0
+}
+
+// A recovery proposal stops taking votes seven days after it was made
+fn recovery_expired(env: &Env, proposal: &RecoveryProposal) -> bool {
+    env.ledger().timestamp() > proposal.proposed_at + PROPOSAL_EXPIRY_SECS
+}
+
+// Swap the member address once the circle's quorum of active members agree;
+// otherwise keep the proposal open
+fn apply_recovery_if_consensus(env: &Env, circle: &mut CircleInfo, proposal: &RecoveryProposal) -> Result<(), SoroSusuError> {
+    let active_members = count_active_members(env, circle);
+    if active_members == 0 {
+        return Err(SoroSusuError::NoActiveMembers);
+    }
+
+    let proposal_key = DataKey::RecoveryProposal(circle.id);
+    let votes = proposal.votes_bitmap.count_ones();
+    if !reaches_quorum(env, circle.id, votes, active_members, MIN_QUORUM_BPS) {
+        env.storage().instance().set(&proposal_key, proposal);
+        return Ok(());
+    }
+
+    move_member(env, circle, &proposal.old_address, &proposal.new_address)?;
+
+    env.storage().instance().remove(&proposal_key);
+    Ok(())
+}
+
+// Upgrade stored data from `version` to `version + 1`
+fn migrate_step(env: &Env, version: u32) -> Result<(), SoroSusuError> {
+    match version {
+        // v2 keeps copies of each circle's member count and token beside CircleInfo; write them for older circles
+        1 => {
+            let circle_count: u64 = env.storage().instance().get(&DataKey::CircleCount).unwrap_or(0);
+            for circle_id in 1..=circle_count {
+                let circle: CircleInfo = match env.storage().instance().get(&DataKey::Circle(circle_id)) {
+                    Some(circle) => circle,
+                    None => continue,
+                };
+                if !env.storage().instance().has(&DataKey::CircleMemberCount(circle_id)) {
+                    env.storage().instance().set(&DataKey::CircleMemberCount(circle_id), &circle.member_count);
+                }
+                if !env.storage().instance().has(&DataKey::CircleToken(circle_id)) {
+                    env.storage().instance().set(&DataKey::CircleToken(circle_id), &circle.token);
+                }
+            }
+            Ok(())
+        }
+        _ => Err(SoroSusuError::InvalidOperation),
+    }
+}
+
+fn award_badge(env: &Env, circle_id: u64, member: &Address, badge: BadgeType) {
+    let key = MetadataKey::MemberBadges(member.clone());
+    let mut badges: Vec<BadgeType> = env.storage().instance().get(&key).unwrap_or_else(|| Vec::new(env));
+    if badges.contains(&badge) {
+        return;
+    }
+    badges.push_back(badge.clone());
+    env.storage().instance().set(&key, &badges);
+    env.events().publish((Symbol::new(env, "BADGE_AWARDED"), circle_id), (member.clone(), badge));
+}
+
+fn award_completion_badges(env: &Env, circle: &CircleInfo) {
+    for member_address in circle.member_addresses.iter() {
+        let member: Option<Member> = env.storage().instance().get(&DataKey::Member(member_address.clone()));
+        if member.is_some_and(|member| member.status == MemberStatus::Active) {
+            award_badge(env, circle.id, &member_address, BadgeType::CircleCompleter);
+        }
+    }
+}
+
+fn record_join(env: &Env, circle_id: u64, member: &Address) {
+    let key = MetadataKey::JoinOrder(circle_id);
+    let mut join_order: Vec<Address> = env.storage().instance().get(&key).unwrap_or_else(|| Vec::new(env));
+    join_order.push_back(member.clone());
+    env.storage().instance().set(&key, &join_order);
+}
+
+// Re-key an active member's seat and per-member entries from one address to another
+fn move_member(env: &Env, circle: &mut CircleInfo, old_address: &Address, new_address: &Address) -> Result<Member, SoroSusuError> {
+    let old_member_key = DataKey::Member(old_address.clone());
+    let mut old_member: Member = env
+        .storage()
+        .instance()
+        .get(&old_member_key)
+        .ok_or(SoroSusuError::NotAMember)?;
+
+    if old_member.status != MemberStatus::Active {
+        return Err(SoroSusuError::MemberNotActive);
+    }
+
+    // The seat being re-keyed has to be this member's own, not whatever sits at their index in another circle
+    if circle.member_addresses.get(old_member.index).as_ref() != Some(old_address) {
+        return Err(SoroSusuError::NotAMember);
+    }
+
+    let new_member_key = DataKey::Member(new_address.clone());
+    if env.storage().instance().has(&new_member_key) {
+        return Err(SoroSusuError::AlreadyMember);
+    }
+
+    old_member.address = new_address.clone();
+    env.storage().instance().set(&new_member_key, &old_member);
+    env.storage().instance().remove(&old_member_key);
+
+    let old_circle_key = DataKey::MemberCircle(old_address.clone());
+    if let Some(circle_id) = env.storage().instance().get::<DataKey, u64>(&old_circle_key) {
+        env.storage().instance().set(&DataKey::MemberCircle(new_address.clone()), &circle_id);
+        env.storage().instance().remove(&old_circle_key);
+    }
+
+    // Seniority, deposit token choice and history follow the member
+    let old_join_key = DataKey::MemberJoinTime(old_address.clone());
+    if let Some(joined_at) = env.storage().instance().get::<DataKey, u64>(&old_join_key) {
+        env.storage().instance().set(&DataKey::MemberJoinTime(new_address.clone()), &joined_at);
+        env.storage().instance().remove(&old_join_key);
+    }
+    let old_token_key = DataKey::MemberToken(old_address.clone());
+    if let Some(token) = env.storage().instance().get::<DataKey, Address>(&old_token_key) {
+        env.storage().instance().set(&DataKey::MemberToken(new_address.clone()), &token);
+        env.storage().instance().remove(&old_token_key);
+    }
+    let old_history_key = DataKey::ContributionHistory(old_address.clone());
+    if let Some(history) = env.storage().instance().get::<DataKey, Vec<u64>>(&old_history_key) {
+        env.storage().instance().set(&DataKey::ContributionHistory(new_address.clone()), &history);
+        env.storage().instance().remove(&old_history_key);
+    }
+    let old_badges_key = MetadataKey::MemberBadges(old_address.clone());
+    if let Some(badges) = env.storage().instance().get::<MetadataKey, Vec<BadgeType>>(&old_badges_key) {
+        env.storage().instance().set(&MetadataKey::MemberBadges(new_address.clone()), &badges);
+        env.storage().instance().remove(&old_badges_key);
+    }
+
+    let mut updated_addresses = Vec::new(env);
+    for i in 0..circle.member_count {
+        let member_address = circle.member_addresses.get(i).unwrap();
+        if i == old_member.index {
+            updated_addresses.push_back(new_address.clone());
+        } else {
+            updated_addresses.push_back(member_address);
+        }
+    }
+    circle.member_addresses = updated_addresses;
 
-    // Set the recipient based on current rotation index
-    let recipient_address = get_member_address_by_index(&env, circle_id, circle.current_recipient_index);
+    if circle.current_pot_recipient == *old_address {
+        circle.current_pot_recipient = new_address.clone();
+    }
+    Ok(old_member)
+}
+
+// Execute finalize round operation
+fn execute_finalize_round(env: &Env, operation: &AdminOperation) -> Result<(), SoroSusuError> {
+    let circle_id = operation.circle_id;
+    let mut circle: CircleInfo = env.storage().instance()
+        .get(&DataKey::Circle(circle_id))
+        .ok_or(SoroSusuError::CircleNotFound)?;
+
+    // Check if round is already finalized
+    if circle.is_round_finalized {
+        return Err(SoroSusuError::RoundAlreadyFinalized);
+    }
+
+    // Every seat gets exactly one round; the circle closes after the last one
+    let round_number: u32 = env.storage().instance().get(&DataKey::RoundNumber(circle_id)).unwrap_or(0);
+    if !circle.is_active || round_number >= circle.max_members {
+        return Err(SoroSusuError::CircleClosed);
+    }
+
+    // An empty bitmap would trivially match an empty circle
+    if circle.member_count == 0 {
+        return Err(SoroSusuError::NoActiveMembers);
+    }
+
+    // Bits past the last seat mean the bitmap was corrupted
+    if circle.contribution_bitmap.checked_shr(circle.member_count).unwrap_or(0) != 0 {
+        return Err(SoroSusuError::InvalidContributionBitmap);
+    }
+
+    // Check if all members have contributed (all bits set in contribution_bitmap)
+    let expected_bitmap = u64::MAX >> (64 - circle.member_count);
+    if circle.contribution_bitmap != expected_bitmap {
+        return Err(SoroSusuError::RoundIncomplete);
+    }
+
+    // Set scheduled payout time (24 hours from now)
+    let current_time = env.ledger().timestamp();
+    let scheduled_payout_time = current_time + 86400; // 24 hours in seconds
+
+    // Set the recipient based on current rotation index, or on trust score in priority mode
+    if circle.priority_ordering {
+        let (recipient_index, _) = select_next_recipient(env, &circle)?;
+        circle.current_recipient_index = recipient_index;
+    }
+    let recipient_address = get_member_address_by_index(&circle, circle.current_recipient_index)
+        .ok_or(SoroSusuError::InvalidRecipientIndex)?;
     circle.current_pot_recipient = recipient_address;
     
     // Update circle state
@@ -339,717 +1471,3085 @@ fn execute_finalize_round(env: &Env, operation: &AdminOperation) {
     
     // Store scheduled payout time
     env.storage().instance().set(&DataKey::ScheduledPayoutTime(circle_id), &scheduled_payout_time);
+    env.storage().instance().set(&DataKey::LastRoundFinalizedAt(circle_id), &current_time);
+
+    // An extension only ever applies to the round it was proposed in
+    env.storage().instance().remove(&DataKey::ExtensionRequest(circle_id));
+    env.storage().instance().remove(&StateKey::DeadlineExtended(circle_id));
     
     // Save updated circle
     env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
 
+    // Remember who paid on time before the bits are reset, so the reserve goes to this round's payers
+    let on_time_bitmap = circle.contribution_bitmap & !circle.late_contribution_bitmap;
+    env.storage().instance().set(&StateKey::OnTimePayers(circle_id), &on_time_bitmap);
+
     // Reset for next round
     circle.contribution_bitmap = 0;
+    circle.late_contribution_bitmap = 0;
     circle.payout_bitmap |= 1 << circle.current_recipient_index;
     circle.current_recipient_index = (circle.current_recipient_index + 1) % circle.max_members;
-    circle.insurance_balance = 0;
     circle.is_insurance_used = false;
+    circle.yield_earned_this_round = 0;
+
+    // An insurance fee that won its vote during the round starts applying now
+    if circle.insurance_fee_votes_bitmap != 0
+        && reaches_quorum(env, circle_id, circle.insurance_fee_votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS)
+    {
+        record_governance_outcome(env, &circle, ProposalType::InsuranceFee, circle.proposed_insurance_fee_bps, circle.insurance_fee_votes_bitmap, true);
+        circle.insurance_fee_bps = circle.proposed_insurance_fee_bps;
+        circle.proposed_insurance_fee_bps = 0;
+        circle.insurance_fee_votes_bitmap = 0;
+    }
+
+    // Likewise a contribution change, so every seat in a round pays the same amount; unpassed ones lapse at expiry
+    if circle.proposed_contribution_amount != 0 {
+        let expiry_key = StateKey::ContributionProposalExpiry(circle_id);
+        let expiry: u64 = env.storage().instance().get(&expiry_key).unwrap_or(u64::MAX);
+        let passed = reaches_quorum(env, circle_id, circle.contribution_votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS);
+        if passed {
+            circle.contribution_amount = circle.proposed_contribution_amount;
+        }
+        if passed || current_time > expiry {
+            circle.proposed_contribution_amount = 0;
+            circle.contribution_votes_bitmap = 0;
+            env.storage().instance().remove(&expiry_key);
+        }
+    }
     
     env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
     
-    // Clear current round contributions for next cycle
+    // Clear current round contributions for next cycle, keeping their total for the recipient
+    let mut round_pot = 0u64;
     for i in 0..circle.member_count {
-        let contribution_key = DataKey::CurrentRoundContribution(circle_id, i as u32);
+        let contribution_key = DataKey::CurrentRoundContribution(circle_id, i);
+        round_pot += env.storage().instance().get::<DataKey, u64>(&contribution_key).unwrap_or(0);
         env.storage().instance().remove(&contribution_key);
+        if let Some(member_address) = circle.member_addresses.get(i) {
+            env.storage().instance().remove(&DataKey::Deposit(circle_id, member_address));
+        }
+    }
+    env.storage().instance().set(&DataKey::RoundPot(circle_id), &round_pot);
+
+    // Freeze the non-payout-token part of the pot so next round's deposits are kept apart
+    let totals_key = DataKey::RoundTokenContributions(circle_id);
+    if let Some(totals) = env.storage().instance().get::<DataKey, Map<Address, u64>>(&totals_key) {
+        env.storage().instance().set(&DataKey::RoundTokenPot(circle_id), &totals);
+        env.storage().instance().remove(&totals_key);
+    }
+
+    advance_frozen_members(env, &circle);
+    apply_tier_changes(env, &circle);
+
+    let round_number = round_number + 1;
+    env.storage().instance().set(&DataKey::RoundNumber(circle_id), &round_number);
+    update_circle_stats(env, circle_id, |stats| stats.total_rounds_completed += 1);
+    if round_number >= circle.max_members {
+        // The last recipient can still claim the pot; everything else stops here
+        award_completion_badges(env, &circle);
+        refund_insurance_on_close(env, &circle)?;
+        circle.is_active = false;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    }
+    Ok(())
+}
+
+// Move each set bit to its seat's new index; `new_index[old] == u32::MAX` drops the bit
+fn compact_bitmap(bitmap: u64, new_index: &[u32; 64]) -> u64 {
+    let mut compacted = 0u64;
+    for (old_index, index) in new_index.iter().enumerate() {
+        if *index != u32::MAX && (bitmap >> old_index) & 1 == 1 {
+            compacted |= 1 << index;
+        }
+    }
+    compacted
+}
+
+// Count another round for each frozen member; two in a row puts their seat up for replacement
+fn advance_frozen_members(env: &Env, circle: &CircleInfo) {
+    for i in 0..circle.member_count {
+        let member_address = circle.member_addresses.get(i).unwrap();
+        let member_key = DataKey::Member(member_address.clone());
+        let mut member: Member = match env.storage().instance().get(&member_key) {
+            Some(member) => member,
+            None => continue,
+        };
+        if member.status != MemberStatus::Frozen {
+            continue;
+        }
+
+        member.frozen_rounds += 1;
+        if member.frozen_rounds >= FROZEN_ROUNDS_BEFORE_EXIT {
+            member.status = MemberStatus::AwaitingReplacement;
+            env.storage().instance().set(&DataKey::PendingExit(circle.id, member_address), &true);
+        }
+        env.storage().instance().set(&member_key, &member);
+    }
+}
+
+// Approved tier changes take effect here, so they never alter a round already under way
+fn apply_tier_changes(env: &Env, circle: &CircleInfo) {
+    for member_address in circle.member_addresses.iter() {
+        let request_key = DataKey::TierChangeRequest(circle.id, member_address.clone());
+        let request: TierChangeRequest = match env.storage().instance().get(&request_key) {
+            Some(request) => request,
+            None => continue,
+        };
+        if !request.approved {
+            continue;
+        }
+
+        let member_key = DataKey::Member(member_address.clone());
+        if let Some(mut member) = env.storage().instance().get::<DataKey, Member>(&member_key) {
+            member.tier_multiplier = request.new_tier;
+            env.storage().instance().set(&member_key, &member);
+            env.events().publish(
+                (Symbol::new(env, "TIER_CHANGED"), circle.id),
+                (member_address, request.new_tier),
+            );
+        }
+        env.storage().instance().remove(&request_key);
+    }
+}
+
+// Active member with the lowest trust score who has not been paid yet; ties go to the earlier joiner
+fn select_next_recipient(env: &Env, circle: &CircleInfo) -> Result<(u32, Address), SoroSusuError> {
+    let mut selected: Option<(u32, Address, u32)> = None;
+    for i in 0..circle.member_count {
+        if circle.payout_bitmap & (1 << i) != 0 {
+            continue;
+        }
+
+        let member_address = circle.member_addresses.get(i).unwrap();
+        let member: Member = match env.storage().instance().get(&DataKey::Member(member_address.clone())) {
+            Some(member) => member,
+            None => continue,
+        };
+        if member.status != MemberStatus::Active {
+            continue;
+        }
+
+        let is_lower = match &selected {
+            Some((_, _, lowest_score)) => member.trust_score < *lowest_score,
+            None => true,
+        };
+        if is_lower {
+            selected = Some((i, member_address, member.trust_score));
+        }
+    }
+
+    selected
+        .map(|(index, address, _)| (index, address))
+        .ok_or(SoroSusuError::NoActiveMembers)
+}
+
+fn insurance_fund(env: &Env, circle_id: u64) -> u64 {
+    env.storage().instance().get(&DataKey::InsuranceFund(circle_id)).unwrap_or(0)
+}
+
+// Insurance must cover the payment and still hold one base contribution afterwards
+fn require_insurance_coverage(env: &Env, circle: &CircleInfo, coverage_amount: u64) -> Result<(), SoroSusuError> {
+    let balance = insurance_fund(env, circle.id);
+    if balance < coverage_amount {
+        return Err(SoroSusuError::InsufficientBalance);
+    }
+
+    if balance - coverage_amount < circle.contribution_amount {
+        return Err(SoroSusuError::SolvencyFloorBreached);
+    }
+    Ok(())
+}
+
+// Pay a covered contribution out of the insurance pool; the caller checks coverage first
+fn spend_insurance(env: &Env, circle_id: u64, amount: u64) {
+    let balance = insurance_fund(env, circle_id);
+    env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &(balance - amount));
+}
+
+fn circle_stats(env: &Env, circle_id: u64) -> CircleStats {
+    env.storage().instance().get(&MetadataKey::CircleStats(circle_id)).unwrap_or(CircleStats {
+        total_rounds_completed: 0,
+        total_volume: 0,
+        total_late_payments: 0,
+        total_insurance_triggers: 0,
+        total_members_joined: 0,
+        total_members_ejected: 0,
+    })
+}
+
+fn update_circle_stats(env: &Env, circle_id: u64, update: impl FnOnce(&mut CircleStats)) {
+    let mut stats = circle_stats(env, circle_id);
+    update(&mut stats);
+    env.storage().instance().set(&MetadataKey::CircleStats(circle_id), &stats);
+}
+
+fn record_governance_outcome(env: &Env, circle: &CircleInfo, proposal_type: ProposalType, proposed_value: u32, votes_bitmap: u64, approved: bool) {
+    let history_key = DataKey::GovernanceHistory(circle.id);
+    let mut history: Vec<VoterTurnout> = env.storage().instance().get(&history_key).unwrap_or_else(|| Vec::new(env));
+    history.push_back(VoterTurnout {
+        proposal_type,
+        proposed_value,
+        votes_cast: votes_bitmap.count_ones(),
+        member_count: circle.member_count,
+        approved,
+        resolved_at: env.ledger().timestamp(),
+    });
+    if history.len() > GOVERNANCE_HISTORY_LEN {
+        history.pop_front();
+    }
+    env.storage().instance().set(&history_key, &history);
+}
+
+// Called wherever a new late fee takes effect; keeps the most recent changes
+fn record_penalty_change(env: &Env, circle_id: u64, new_bps: u32) {
+    let history_key = DataKey::PenaltyChangeHistory(circle_id);
+    let mut history: Vec<(u32, u64)> = env.storage().instance().get(&history_key).unwrap_or_else(|| Vec::new(env));
+    history.push_back((new_bps, env.ledger().timestamp()));
+    if history.len() > PENALTY_HISTORY_LEN {
+        history.pop_front();
+    }
+    env.storage().instance().set(&history_key, &history);
+}
+
+// Keeps the most recent claims; the oldest entry is dropped once the log is full
+fn record_insurance_claim(env: &Env, circle_id: u64, beneficiary: &Address, amount: u64) {
+    let history_key = DataKey::InsuranceClaimHistory(circle_id);
+    let mut history: Vec<(u64, Address, u64)> = env.storage().instance().get(&history_key).unwrap_or_else(|| Vec::new(env));
+    history.push_back((env.ledger().timestamp(), beneficiary.clone(), amount));
+    if history.len() > INSURANCE_CLAIM_HISTORY_LEN {
+        history.pop_front();
+    }
+    env.storage().instance().set(&history_key, &history);
+    update_circle_stats(env, circle_id, |stats| stats.total_insurance_triggers += 1);
+}
+
+// Everything that must hold for insurance to cover `member` this round; returns the member
+fn check_insurance_trigger(env: &Env, circle: &CircleInfo, member: &Address) -> Result<Member, SoroSusuError> {
+    // Check if insurance was already used this cycle
+    if circle.is_insurance_used {
+        return Err(SoroSusuError::InsuranceAlreadyUsed);
+    }
+
+    let member_info: Member = env.storage().instance()
+        .get(&DataKey::Member(member.clone()))
+        .ok_or(SoroSusuError::NotAMember)?;
+
+    // Frozen members can still be covered by insurance
+    if member_info.status != MemberStatus::Active && member_info.status != MemberStatus::Frozen {
+        return Err(SoroSusuError::MemberNotActive);
+    }
+
+    // Check if there is enough balance for the tier-adjusted contribution
+    let member_contribution_amount = circle.contribution_amount * member_info.tier_multiplier as u64;
+    require_insurance_coverage(env, circle, member_contribution_amount)?;
+
+    if (circle.contribution_bitmap & (1 << member_info.index)) != 0 {
+        return Err(SoroSusuError::AlreadyContributed);
+    }
+    Ok(member_info)
+}
+
+// Execute trigger insurance operation
+fn execute_trigger_insurance(env: &Env, operation: &AdminOperation) -> Result<(), SoroSusuError> {
+    let circle_id = operation.circle_id;
+    let target_member = operation
+        .target_member
+        .clone()
+        .ok_or(SoroSusuError::InvalidOperation)?;
+    
+    let mut circle: CircleInfo = env.storage().instance()
+        .get(&DataKey::Circle(circle_id))
+        .ok_or(SoroSusuError::CircleNotFound)?;
+
+    let member_info = check_insurance_trigger(env, &circle, &target_member)?;
+    let member_contribution_amount = circle.contribution_amount * member_info.tier_multiplier as u64;
+
+    circle.contribution_bitmap |= 1 << member_info.index;
+    spend_insurance(env, circle_id, member_contribution_amount);
+    circle.is_insurance_used = true;
+    record_insurance_claim(env, circle_id, &target_member, member_contribution_amount);
+    
+    // Track the insurance contribution for current round
+    let contribution_key = DataKey::CurrentRoundContribution(circle_id, member_info.index);
+    env.storage().instance().set(&contribution_key, &member_contribution_amount);
+
+    env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    Ok(())
+}
+
+// Split the leftover insurance pool among active members by how many deposits each made
+fn refund_insurance_on_close(env: &Env, circle: &CircleInfo) -> Result<(), SoroSusuError> {
+    let pool = insurance_fund(env, circle.id);
+    if pool == 0 {
+        return Ok(());
+    }
+
+    let mut recipients: Vec<(Address, u32)> = Vec::new(env);
+    let mut total_contributions = 0u64;
+    for i in 0..circle.member_count {
+        let member_address = circle.member_addresses.get(i).ok_or(SoroSusuError::InvalidRecipientIndex)?;
+        if let Some(member) = env.storage().instance().get::<DataKey, Member>(&DataKey::Member(member_address.clone())) {
+            if member.status == MemberStatus::Active && member.contribution_count > 0 {
+                total_contributions += member.contribution_count as u64;
+                recipients.push_back((member_address, member.contribution_count));
+            }
+        }
+    }
+    if total_contributions == 0 {
+        return Ok(());
+    }
+
+    let token_client = token::Client::new(env, &circle.token);
+    let mut remaining = pool;
+    for (member_address, contribution_count) in recipients.iter() {
+        let share = ((pool as u128 * contribution_count as u128) / total_contributions as u128) as u64;
+        if share == 0 {
+            continue;
+        }
+
+        token_client.transfer(&env.current_contract_address(), &member_address, &(share as i128));
+        remaining -= share;
+
+        env.events().publish(
+            (Symbol::new(env, "INSURANCE_REFUNDED"), circle.id),
+            (member_address, share),
+        );
+    }
+    env.storage().instance().set(&DataKey::InsuranceFund(circle.id), &remaining);
+    Ok(())
+}
+
+// Resize the circle to the approved maximum, dropping contribution bits for seats that no longer exist
+fn apply_max_members_change(circle: &mut CircleInfo) -> Result<(), SoroSusuError> {
+    // Members may have joined while the vote was open
+    if circle.proposed_max_members < circle.member_count {
+        return Err(SoroSusuError::MaxMembersBelowMemberCount);
+    }
+
+    circle.max_members = circle.proposed_max_members;
+    if circle.max_members < 64 {
+        circle.contribution_bitmap &= (1u64 << circle.max_members) - 1;
+    }
+    circle.proposed_max_members = 0;
+    circle.max_members_votes_bitmap = 0;
+    Ok(())
+}
+
+fn is_approved_lending_pool(env: &Env, pool: &Address) -> bool {
+    env.storage().instance().get(&DataKey::ApprovedLendingPool(pool.clone())).unwrap_or(false)
+}
+
+// Payout-token funds the contract holds for this circle's pots: the open round's contributions and a
+// finalized pot not yet claimed. Every circle shares one token balance, so this is the most a circle may move.
+fn circle_pot_balance(env: &Env, circle: &CircleInfo) -> u64 {
+    let mut held = 0u64;
+    for i in 0..circle.member_count {
+        let amount: u64 = match env.storage().instance().get(&DataKey::CurrentRoundContribution(circle.id, i)) {
+            Some(amount) => amount,
+            None => continue,
+        };
+        // Insurance-covered seats have no deposit token; their cover came out of the payout-token pool
+        let deposit_token: Option<Address> = circle.member_addresses.get(i)
+            .and_then(|address| env.storage().instance().get(&DataKey::Deposit(circle.id, address)));
+        if deposit_token.is_none_or(|token| token == circle.token) {
+            held += amount;
+        }
+    }
+
+    (held + unclaimed_pot(env, circle)).saturating_sub(circle.yield_deposited)
+}
+
+// Payout-token part of a finalized pot that its recipient has not claimed yet
+fn unclaimed_pot(env: &Env, circle: &CircleInfo) -> u64 {
+    if !circle.is_round_finalized {
+        return 0;
+    }
+    let round_pot: u64 = env.storage().instance().get(&DataKey::RoundPot(circle.id)).unwrap_or(0);
+    let token_pot: Map<Address, u64> = env.storage().instance().get(&DataKey::RoundTokenPot(circle.id)).unwrap_or_else(|| Map::new(env));
+    round_pot.saturating_sub(token_pot.values().iter().sum())
+}
+
+// The circle's own yield strategy if it chose one, the protocol-wide lending pool otherwise
+fn circle_lending_pool(env: &Env, circle_id: u64) -> Result<Address, SoroSusuError> {
+    if let Some(pool) = env.storage().instance().get(&DataKey::YieldStrategy(circle_id)) {
+        return Ok(pool);
+    }
+    env.storage().instance().get(&DataKey::LendingPool).ok_or(SoroSusuError::LendingPoolNotConfigured)
+}
+
+// Recall everything supplied to the lending pool and book whatever came back above principal as earnings
+fn withdraw_yield(env: &Env, circle: &mut CircleInfo) -> Result<(), SoroSusuError> {
+    let lending_pool = circle_lending_pool(env, circle.id)?;
+
+    let token_client = token::Client::new(env, &circle.token);
+    let balance_before = token_client.balance(&env.current_contract_address());
+
+    let lending_client = LendingPoolClient::new(env, &lending_pool);
+    lending_client.withdraw(
+        &circle.token,
+        &env.current_contract_address(),
+        &circle.yield_deposited,
+    );
+
+    let withdrawn = (token_client.balance(&env.current_contract_address()) - balance_before).max(0) as u64;
+    let earned = withdrawn.saturating_sub(circle.yield_deposited);
+    circle.yield_earned_this_round += earned;
+    circle.yield_deposited = 0;
+
+    env.events().publish(
+        (Symbol::new(env, "YIELD_EARNED"), circle.id),
+        earned,
+    );
+    Ok(())
+}
+
+// Record one member's contribution for the round, pulling the tokens from payer
+fn process_deposit(env: &Env, payer: &Address, user: &Address, circle_id: u64) -> Result<(), SoroSusuError> {
+    // 2. Load the Circle Data
+    let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+    let current_time = env.ledger().timestamp();
+
+    if !circle.is_active {
+        return Err(SoroSusuError::CircleClosed);
+    }
+
+    apply_due_contribution_change(env, &mut circle, current_time);
+
+    // Keep pot liquid before deadline by recalling supplied funds.
+    let recall_buffer = match circle_settings(env, circle_id).max_yield_idle_secs {
+        0 => protocol_config(env).yield_liquidity_buffer_secs,
+        secs => secs,
+    };
+    if circle.yield_deposited > 0 && current_time + recall_buffer >= circle.deadline_timestamp {
+        withdraw_yield(env, &mut circle)?;
+    }
+
+    // 3. Check if user is actually a member of this circle
+    let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+    if member_circle != Some(circle_id) {
+        return Err(SoroSusuError::NotAMember);
+    }
+    let member_key = DataKey::Member(user.clone());
+    let mut member: Member = env.storage().instance().get(&member_key)
+        .ok_or(SoroSusuError::NotAMember)?;
+
+    if member.status != MemberStatus::Active {
+        return Err(SoroSusuError::MemberNotActive);
+    }
+
+    // One deposit per member per round
+    if (circle.contribution_bitmap >> member.index) & 1 == 1 {
+        return Err(SoroSusuError::AlreadyContributed);
+    }
+
+    // 4. Create the Token Client
+    let client = token::Client::new(env, &circle.token);
+
+    // 5. Work out what this member owes: tiered contribution, insurance fee and any late penalty
+    let (member_contribution_amount, insurance_fee, penalty_amount) = deposit_cost(env, &circle, &member, current_time);
+
+    // Measured against the full round pot; a running total would reject whoever pays first
+    if circle.max_single_member_share_bps > 0 {
+        let round_pot = expected_round_pot(env, &circle);
+        if (member_contribution_amount as u128) * 10000
+            > (round_pot as u128) * circle.max_single_member_share_bps as u128
+        {
+            return Err(SoroSusuError::ShareLimitExceeded);
+        }
+    }
+
+    let is_late = current_time > circle.deadline_timestamp + circle.grace_period_secs;
+    if is_late {
+        // Update Group Reserve balance
+        let mut reserve_balance: u64 = env.storage().instance().get(&DataKey::GroupReserve).unwrap_or(0);
+        reserve_balance += penalty_amount;
+        env.storage().instance().set(&DataKey::GroupReserve, &reserve_balance);
+
+        // Update this circle's share of the reserve
+        let circle_reserve_key = DataKey::CircleReserve(circle_id);
+        let circle_reserve: u64 = env.storage().instance().get(&circle_reserve_key).unwrap_or(0);
+        env.storage().instance().set(&circle_reserve_key, &(circle_reserve + penalty_amount));
+
+        circle.late_contribution_bitmap |= 1 << member.index;
+    }
+
+    // 6. Transfer the full amount (including any penalty) from the payer
+    let total_amount = member_contribution_amount + insurance_fee + penalty_amount;
+
+    let deposit_token = member_deposit_token(env, &circle, user);
+    if deposit_token == circle.token {
+        let total_amount_i128 = total_amount as i128;
+        client.transfer(
+            payer, 
+            &env.current_contract_address(), 
+            &total_amount_i128
+        );
+    } else {
+        // Only the contribution itself may use another accepted token; fees and penalties fund
+        // pools that are paid out in the payout token
+        let deposit_client = token::Client::new(env, &deposit_token);
+        deposit_client.transfer(payer, &env.current_contract_address(), &(member_contribution_amount as i128));
+        if insurance_fee + penalty_amount > 0 {
+            client.transfer(payer, &env.current_contract_address(), &((insurance_fee + penalty_amount) as i128));
+        }
+
+        let totals_key = DataKey::RoundTokenContributions(circle_id);
+        let mut totals: Map<Address, u64> = env.storage().instance().get(&totals_key).unwrap_or_else(|| Map::new(env));
+        let collected = totals.get(deposit_token.clone()).unwrap_or(0);
+        totals.set(deposit_token.clone(), collected + member_contribution_amount);
+        env.storage().instance().set(&totals_key, &totals);
+    }
+
+    let volume: u128 = env.storage().instance().get(&DataKey::TotalProtocolVolume).unwrap_or(0);
+    let volume = volume.checked_add(total_amount as u128).ok_or(SoroSusuError::InvalidAmount)?;
+    env.storage().instance().set(&DataKey::TotalProtocolVolume, &volume);
+    update_circle_stats(env, circle_id, |stats| {
+        stats.total_volume += total_amount as u128;
+        if is_late {
+            stats.total_late_payments += 1;
+        }
+    });
+
+    if insurance_fee > 0 {
+        let balance = insurance_fund(env, circle_id);
+        env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &(balance + insurance_fee));
+    }
+
+    // 7. Update member contribution info
+    member.contribution_count += 1;
+    member.last_contribution_time = current_time;
+    member.total_contributed += member_contribution_amount;
+    member.trust_score = if is_late {
+        member.trust_score.saturating_sub(TRUST_SCORE_LATE_PENALTY)
+    } else {
+        core::cmp::min(member.trust_score + TRUST_SCORE_ON_TIME_GAIN, MAX_TRUST_SCORE)
+    };
+    if is_late {
+        member.late_contributions += 1;
+    }
+    
+    // 8. Save updated member info
+    env.storage().instance().set(&member_key, &member);
+
+    if member.contribution_count == ON_TIME_PAYER_DEPOSITS && member.late_contributions == 0 {
+        award_badge(env, circle_id, user, BadgeType::OnTimePayer);
+    }
+    let joined_at: u64 = env.storage().instance().get(&DataKey::MemberJoinTime(user.clone())).unwrap_or(current_time);
+    if current_time - joined_at >= LONG_TERM_MEMBER_SECS {
+        award_badge(env, circle_id, user, BadgeType::LongTermMember);
+    }
+
+    let history_key = DataKey::ContributionHistory(user.clone());
+    let mut history: Vec<u64> = env.storage().instance().get(&history_key).unwrap_or_else(|| Vec::new(env));
+    history.push_back(current_time);
+    if history.len() > CONTRIBUTION_HISTORY_LEN {
+        history.pop_front();
+    }
+    env.storage().instance().set(&history_key, &history);
+
+    // 9. Track individual contribution for current round
+    let contribution_key = DataKey::CurrentRoundContribution(circle_id, member.index);
+    env.storage().instance().set(&contribution_key, &member_contribution_amount);
+    env.storage().instance().set(&DataKey::Deposit(circle_id, user.clone()), &deposit_token);
+
+    // 10. Update circle deadline for next cycle
+    circle.deadline_timestamp = current_time + circle.cycle_duration;
+    circle.contribution_bitmap |= 1 << member.index;
+
+    // Emit a health snapshot for indexers/frontends; ejected, frozen and departing seats do not count.
+    let active_members = count_active_members(env, &circle);
+    let contributed_members = core::cmp::min(circle.contribution_bitmap.count_ones(), active_members);
+    let missed_payments = active_members.saturating_sub(contributed_members);
+    let trust_score = (contributed_members * 100)
+        .checked_div(active_members)
+        .unwrap_or(0);
+
+    let health_update = GroupHealthUpdateEvent {
+        group_id: circle_id,
+        missed_payments,
+        active_members,
+        trust_score,
+    };
+    env.events()
+        .publish((Symbol::new(env, "GROUP_HEALTH"), circle_id), health_update);
+
+    env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+    Ok(())
+}
+
+#[contractclient(name = "SusuNftClient")]
+pub trait SusuNftTrait {
+    fn mint(env: Env, to: Address, token_id: u128);
+    fn burn(env: Env, from: Address, token_id: u128);
+}
+
+#[contractclient(name = "LendingPoolClient")]
+pub trait LendingPoolTrait {
+    fn supply(env: Env, token: Address, from: Address, amount: u64);
+    fn withdraw(env: Env, token: Address, to: Address, amount: u64);
+    fn get_lending_pool_version(env: Env) -> u32;
+}
+
+// --- IMPLEMENTATION ---
+
+#[contract]
+pub struct SoroSusu;
+
+#[contractimpl]
+impl SoroSusuTrait for SoroSusu {
+    fn init(env: Env, admin: Address) -> Result<(), SoroSusuError> {
+        // Initialize the circle counter to 0 if it doesn't exist
+        if !env.storage().instance().has(&DataKey::CircleCount) {
+            env.storage().instance().set(&DataKey::CircleCount, &0u64);
+        }
+        if !env.storage().instance().has(&DataKey::ContractVersion) {
+            env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        }
+        // Set the admin
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        // The protocol admin is the sole multi-sig signer until configured otherwise
+        if !env.storage().instance().has(&DataKey::AdminList) {
+            let mut admins = Vec::new(&env);
+            admins.push_back(admin);
+            env.storage().instance().set(&DataKey::AdminList, &admins);
+            env.storage().instance().set(&DataKey::ApprovalThreshold, &1u32);
+        }
+
+        Ok(())
+    }
+
+    fn set_lending_pool(env: Env, admin: Address, pool: Address, version: u32) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::LendingPool, &pool);
+        env.storage().instance().set(&DataKey::LendingPoolVersion, &version);
+
+        Ok(())
+    }
+
+    fn approve_lending_pool(env: Env, admin: Address, pool: Address) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::ApprovedLendingPool(pool), &true);
+
+        Ok(())
+    }
+
+    fn revoke_lending_pool(env: Env, admin: Address, pool: Address) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        // Circles already on this pool can still recall from it, but cannot supply to it again
+        env.storage().instance().remove(&DataKey::ApprovedLendingPool(pool));
+
+        Ok(())
+    }
+
+    fn block_address(env: Env, admin: Address, target: Address) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::BlockedAddress(target), &true);
+
+        Ok(())
+    }
+
+    fn unblock_address(env: Env, admin: Address, target: Address) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().remove(&DataKey::BlockedAddress(target));
+
+        Ok(())
+    }
+
+    fn pause_protocol(env: Env, admin: Address) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::ProtocolPaused, &true);
+        env.events().publish((Symbol::new(&env, "PROTOCOL_PAUSED"),), admin);
+
+        Ok(())
+    }
+
+    fn resume_protocol(env: Env, admin: Address) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().remove(&DataKey::ProtocolPaused);
+        env.events().publish((Symbol::new(&env, "PROTOCOL_RESUMED"),), admin);
+
+        Ok(())
+    }
+
+    fn get_contract_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::ContractVersion).unwrap_or(1)
+    }
+
+    fn migrate(env: Env, admin: Address, from_version: u32, to_version: u32) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        // The caller states what they expect to find, so a migration is never applied twice or out of order
+        if from_version != Self::get_contract_version(env.clone()) || to_version <= from_version || to_version > CONTRACT_VERSION {
+            return Err(SoroSusuError::InvalidOperation);
+        }
+
+        for version in from_version..to_version {
+            migrate_step(&env, version)?;
+        }
+        env.storage().instance().set(&DataKey::ContractVersion, &to_version);
+        env.events().publish((Symbol::new(&env, "CONTRACT_MIGRATED"),), (from_version, to_version));
+
+        Ok(())
+    }
+
+    fn get_protocol_config(env: Env) -> ProtocolConfig {
+        protocol_config(&env)
+    }
+
+    fn set_protocol_config(env: Env, admin: Address, config: ProtocolConfig) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        let durations = [config.yield_liquidity_buffer_secs, config.duration_change_notice_secs, config.min_cycle_duration_secs];
+        if durations.iter().any(|secs| *secs == 0 || *secs > MAX_CYCLE_DURATION_SECS) {
+            return Err(SoroSusuError::InvalidCycleDuration);
+        }
+
+        env.storage().instance().set(&DataKey::ProtocolConfig, &config);
+
+        Ok(())
+    }
+
+    fn set_purge_after_secs(env: Env, admin: Address, purge_after_secs: u64) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::PurgeAfterSecs, &purge_after_secs);
+
+        Ok(())
+    }
+
+    fn create_circle(env: Env, creator: Address, amount: u64, max_members: u32, token: Address, cycle_duration: u64, grace_period_secs: u64, insurance_fee_bps: u32, max_single_member_share_bps: u32, nft_contract: Address) -> Result<u64, SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        // 1. Get the current Circle Count
+        let mut circle_count: u64 = env.storage().instance().get(&DataKey::CircleCount).unwrap_or(0);
+        
+        // 2. Increment the ID for the new circle; ids are never reused
+        circle_count = circle_count.checked_add(1).ok_or(SoroSusuError::InvalidOperation)?;
+
+        if max_members > 64 {
+            return Err(SoroSusuError::MaxMembersExceeded);
+        }
+
+        if amount < MIN_CONTRIBUTION_AMOUNT {
+            return Err(SoroSusuError::InvalidAmount);
+        }
+
+        if insurance_fee_bps > MAX_INSURANCE_FEE_BPS {
+            return Err(SoroSusuError::InvalidBasisPoints);
+        }
+
+        if max_single_member_share_bps > 10000 {
+            return Err(SoroSusuError::InvalidBasisPoints);
+        }
+
+        if cycle_duration < protocol_config(&env).min_cycle_duration_secs {
+            return Err(SoroSusuError::InvalidCycleDuration);
+        }
+
+        if cycle_duration > MAX_CYCLE_DURATION_SECS {
+            return Err(SoroSusuError::InvalidCycleDuration);
+        }
+
+        if grace_period_secs >= cycle_duration {
+            return Err(SoroSusuError::InvalidGracePeriod);
+        }
+
+        // 3. Create the Circle Data Struct
+        let current_time = env.ledger().timestamp();
+        let new_circle = CircleInfo {
+            id: circle_count,
+            creator: creator.clone(),
+            contribution_amount: amount,
+            max_members,
+            member_count: 0,
+            current_recipient_index: 0,
+            is_active: true,
+            token: token.clone(),
+            deadline_timestamp: current_time + cycle_duration,
+            cycle_duration,
+            grace_period_secs,
+            pending_cycle_duration: 0,
+            duration_change_effective_at: 0,
+            contribution_bitmap: 0,
+            late_contribution_bitmap: 0,
+            payout_bitmap: 0,
+            insurance_fee_bps,
+            is_insurance_used: false,
+            max_single_member_share_bps,
+            late_fee_bps: 100, // Default 1%
+            proposed_late_fee_bps: 0,
+            proposal_votes_bitmap: 0,
+            proposed_contribution_amount: 0,
+            contribution_votes_bitmap: 0,
+            nft_contract,
+            nft_contract_version: 0,
+            is_round_finalized: false,
+            current_pot_recipient: creator.clone(), // Initialize with creator
+            member_addresses: Vec::new(&env), // Initialize empty member addresses vector
+            yield_deposited: 0,
+            refund_ejected_members: false,
+            priority_ordering: false,
+            is_invite_only: false,
+            yield_earned_this_round: 0,
+            proposed_max_members: 0,
+            max_members_votes_bitmap: 0,
+            accepted_tokens: Vec::from_array(&env, [token.clone()]),
+            proposed_insurance_fee_bps: 0,
+            insurance_fee_votes_bitmap: 0,
+        };
+
+        // 4. Save the Circle and the new Count
+        env.storage().instance().set(&DataKey::Circle(circle_count), &new_circle);
+        env.storage().instance().set(&DataKey::CircleCount, &circle_count);
+        env.storage().instance().set(&DataKey::CircleMemberCount(circle_count), &0u32);
+        env.storage().instance().set(&DataKey::CircleToken(circle_count), &token);
+
+        let mut settings = circle_settings(&env, circle_count);
+        settings.created_at = current_time;
+        env.storage().instance().set(&DataKey::CircleSettings(circle_count), &settings);
+
+        let creator_key = DataKey::CirclesByCreator(creator);
+        let mut creator_circles: Vec<u64> = env.storage().instance().get(&creator_key).unwrap_or_else(|| Vec::new(&env));
+        creator_circles.push_back(circle_count);
+        env.storage().instance().set(&creator_key, &creator_circles);
+        tag_circle_category(&env, circle_count, CircleCategory::Savings);
+
+        // 5. Initialize Group Reserve if not exists
+        if !env.storage().instance().has(&DataKey::GroupReserve) {
+            env.storage().instance().set(&DataKey::GroupReserve, &0u64);
+        }
+
+        // 6. Return the new ID
+        Ok(circle_count)
+    }
+
+    fn clone_circle(env: Env, creator: Address, source_circle_id: u64) -> Result<u64, SoroSusuError> {
+        // The creator signs through create_circle below; a second require_auth in this frame would be rejected
+        require_protocol_active(&env)?;
+
+        let source: CircleInfo = env.storage().instance().get(&DataKey::Circle(source_circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let circle_id = Self::create_circle(
+            env.clone(),
+            creator,
+            source.contribution_amount,
+            source.max_members,
+            source.token.clone(),
+            source.cycle_duration,
+            source.grace_period_secs,
+            source.insurance_fee_bps,
+            source.max_single_member_share_bps,
+            source.nft_contract.clone(),
+        )?;
+
+        // Settings changed after creation carry over too; members, votes and balances do not
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        circle.late_fee_bps = source.late_fee_bps;
+        circle.refund_ejected_members = source.refund_ejected_members;
+        circle.priority_ordering = source.priority_ordering;
+        circle.is_invite_only = source.is_invite_only;
+        circle.accepted_tokens = source.accepted_tokens.clone();
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        let mut settings = circle_settings(&env, circle_id);
+        let source_settings = circle_settings(&env, source_circle_id);
+        settings.quorum_threshold_bps = source_settings.quorum_threshold_bps;
+        settings.max_yield_idle_secs = source_settings.max_yield_idle_secs;
+        env.storage().instance().set(&DataKey::CircleSettings(circle_id), &settings);
+        tag_circle_category(&env, circle_id, Self::get_circle_category(env.clone(), source_circle_id)?);
+
+        Ok(circle_id)
+    }
+
+    fn get_circles_by_creator(env: Env, creator: Address) -> Result<Vec<u64>, SoroSusuError> {
+        Ok(env.storage().instance()
+            .get(&DataKey::CirclesByCreator(creator))
+            .unwrap_or_else(|| Vec::new(&env)))
+    }
+
+    fn get_active_circles(env: Env, start: u64, limit: u64) -> Result<Vec<u64>, SoroSusuError> {
+        let circle_count: u64 = env.storage().instance().get(&DataKey::CircleCount).unwrap_or(0);
+        let end = start.saturating_add(limit.min(MAX_CIRCLE_PAGE_SIZE)).min(circle_count.saturating_add(1));
+
+        let mut active = Vec::new(&env);
+        for circle_id in start..end {
+            if let Some(circle) = env.storage().instance().get::<DataKey, CircleInfo>(&DataKey::Circle(circle_id)) {
+                if circle.is_active {
+                    active.push_back(circle_id);
+                }
+            }
+        }
+        Ok(active)
+    }
+
+    fn get_circle_token(env: Env, circle_id: u64) -> Result<Address, SoroSusuError> {
+        if let Some(token) = env.storage().instance().get(&DataKey::CircleToken(circle_id)) {
+            return Ok(token);
+        }
+        // Circles created before the token was kept separately
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(circle.token)
+    }
+
+    fn join_circle(env: Env, user: Address, circle_id: u64, tier_multiplier: u32) -> Result<(), SoroSusuError> {
+        Self::join_circle_with_referrer(env, user, circle_id, tier_multiplier, None)
+    }
+
+    fn join_circle_with_referrer(env: Env, user: Address, circle_id: u64, tier_multiplier: u32, referrer: Option<Address>) -> Result<(), SoroSusuError> {
+        // 1. Authorization: The user MUST sign this transaction
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        // 2. Retrieve the circle data
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        add_member(&env, &mut circle, &user, tier_multiplier, referrer, true)?;
+
+        // Save the updated circle back to storage
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn batch_join(env: Env, creator: Address, circle_id: u64, members: Vec<Address>, tier_multipliers: Vec<u32>) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        if members.len() != tier_multipliers.len() {
+            return Err(SoroSusuError::BatchLengthMismatch);
+        }
+        if circle.member_count + members.len() > circle.max_members {
+            return Err(SoroSusuError::CircleFull);
+        }
+
+        // The creator vouches for every address, so no invites are needed; any failure reverts the whole batch
+        for (member, tier_multiplier) in members.iter().zip(tier_multipliers.iter()) {
+            add_member(&env, &mut circle, &member, tier_multiplier, None, false)?;
+        }
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn set_invite_only(env: Env, creator: Address, circle_id: u64, enabled: bool) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        circle.is_invite_only = enabled;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn set_circle_description(env: Env, creator: Address, circle_id: u64, name: Symbol, description: Bytes) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        if name == Symbol::new(&env, "") {
+            return Err(SoroSusuError::InvalidOperation);
+        }
+
+        env.storage().instance().set(&MetadataKey::CircleName(circle_id), &name);
+        env.storage().instance().set(&MetadataKey::CircleDescription(circle_id), &description);
+
+        Ok(())
+    }
+
+    fn get_circle_description(env: Env, circle_id: u64) -> Result<(Symbol, Bytes), SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+
+        // Circles that were never described read as an empty name and description
+        let name = env.storage().instance().get(&MetadataKey::CircleName(circle_id)).unwrap_or_else(|| Symbol::new(&env, ""));
+        let description = env.storage().instance().get(&MetadataKey::CircleDescription(circle_id)).unwrap_or_else(|| Bytes::new(&env));
+        Ok((name, description))
+    }
+
+    fn set_circle_category(env: Env, creator: Address, circle_id: u64, category: CircleCategory) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        tag_circle_category(&env, circle_id, category);
+
+        Ok(())
+    }
+
+    fn get_circle_category(env: Env, circle_id: u64) -> Result<CircleCategory, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        // Circles created before categories existed count as savings circles
+        Ok(env.storage().instance().get(&MetadataKey::CircleCategory(circle_id)).unwrap_or(CircleCategory::Savings))
+    }
+
+    fn get_circles_by_category(env: Env, category: CircleCategory, start: u64, limit: u64) -> Result<Vec<u64>, SoroSusuError> {
+        let ids: Vec<u64> = env.storage().instance().get(&MetadataKey::CategoryIndex(category)).unwrap_or_else(|| Vec::new(&env));
+        let end = start.saturating_add(limit.min(MAX_CIRCLE_PAGE_SIZE)).min(ids.len() as u64);
+
+        let mut page = Vec::new(&env);
+        for position in start..end {
+            page.push_back(ids.get(position as u32).unwrap());
+        }
+        Ok(page)
+    }
+
+    fn issue_invite(env: Env, creator: Address, circle_id: u64, invitee: Address) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::Invite(circle_id, invitee), &true);
+
+        Ok(())
+    }
+
+    fn revoke_invite(env: Env, creator: Address, circle_id: u64, invitee: Address) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        env.storage().instance().remove(&DataKey::Invite(circle_id, invitee));
+
+        Ok(())
+    }
+
+    fn deposit(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        // 1. Authorization: The user must sign this!
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        process_deposit(&env, &user, &user, circle_id)
+    }
+
+    fn deposit_with_note(env: Env, user: Address, circle_id: u64, note: Symbol) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        process_deposit(&env, &user, &user, circle_id)?;
+
+        env.events().publish(
+            (Symbol::new(&env, "DEPOSIT_WITH_NOTE"), circle_id),
+            (user, note),
+        );
+
+        Ok(())
+    }
+
+    fn simulate_deposit(env: Env, user: Address, circle_id: u64) -> Result<(u64, u64, u64), SoroSusuError> {
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let member: Member = env.storage().instance().get(&DataKey::Member(user)).ok_or(SoroSusuError::NotAMember)?;
+
+        // Quote what deposit would charge without committing a due contribution change
+        let current_time = env.ledger().timestamp();
+        let settings = circle_settings(&env, circle_id);
+        if settings.pending_contribution_amount != 0 && current_time >= settings.contribution_effective_at {
+            circle.contribution_amount = settings.pending_contribution_amount;
+        }
+
+        Ok(deposit_cost(&env, &circle, &member, current_time))
+    }
+
+    fn deposit_on_behalf(env: Env, payer: Address, beneficiary: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        payer.require_auth();
+        require_protocol_active(&env)?;
+
+        require_circle_exists(&env, circle_id)?;
+        let beneficiary_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(beneficiary.clone()));
+        if beneficiary_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+
+        process_deposit(&env, &payer, &beneficiary, circle_id)
+    }
+
+    fn bulk_deposit(env: Env, payer: Address, circle_id: u64, members: Vec<Address>) -> Result<(), SoroSusuError> {
+        // The payer funds every contribution; each member still has to sign off on their own deposit
+        payer.require_auth();
+        require_protocol_active(&env)?;
+
+        for member in members.iter() {
+            member.require_auth();
+            process_deposit(&env, &payer, &member, circle_id)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_trust_score(env: Env, member: Address) -> Result<u32, SoroSusuError> {
+        let member_info: Member = env.storage().instance()
+            .get(&DataKey::Member(member))
+            .ok_or(SoroSusuError::NotAMember)?;
+        Ok(member_info.trust_score)
+    }
+
+    fn get_contribution_history(env: Env, member: Address) -> Result<Vec<u64>, SoroSusuError> {
+        Ok(env.storage().instance()
+            .get(&DataKey::ContributionHistory(member))
+            .unwrap_or_else(|| Vec::new(&env)))
+    }
+
+    fn get_protocol_volume(env: Env) -> Result<u128, SoroSusuError> {
+        Ok(env.storage().instance().get(&DataKey::TotalProtocolVolume).unwrap_or(0))
+    }
+
+    fn distribute_group_reserve(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id))
+            .ok_or(SoroSusuError::CircleNotFound)?;
+        let protocol_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if caller != circle.creator && caller != protocol_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        let circle_reserve_key = DataKey::CircleReserve(circle_id);
+        let circle_reserve: u64 = env.storage().instance().get(&circle_reserve_key).unwrap_or(0);
+        if circle_reserve == 0 {
+            return Err(SoroSusuError::ReserveEmpty);
+        }
+
+        // Eligible members paid the last finalized round and were not late; each round is shared out once
+        let on_time_key = StateKey::OnTimePayers(circle_id);
+        let on_time_bitmap: u64 = env.storage().instance().get(&on_time_key).ok_or(SoroSusuError::RoundNotFinalized)?;
+        env.storage().instance().remove(&on_time_key);
+        let on_time_count = on_time_bitmap.count_ones();
+        if on_time_count == 0 {
+            // Nobody qualifies; keep the reserve for a later round
+            return Ok(());
+        }
+
+        let share = circle_reserve / on_time_count as u64;
+        if share == 0 {
+            return Ok(());
+        }
+
+        let client = token::Client::new(&env, &circle.token);
+        for i in 0..circle.member_count {
+            if on_time_bitmap & (1 << i) != 0 {
+                let recipient = circle.member_addresses.get(i).ok_or(SoroSusuError::InvalidRecipientIndex)?;
+                client.transfer(&env.current_contract_address(), &recipient, &(share as i128));
+            }
+        }
+
+        // Any rounding remainder stays in the reserve
+        let distributed = share * on_time_count as u64;
+        env.storage().instance().set(&circle_reserve_key, &(circle_reserve - distributed));
+
+        let reserve_balance: u64 = env.storage().instance().get(&DataKey::GroupReserve).unwrap_or(0);
+        env.storage().instance().set(&DataKey::GroupReserve, &reserve_balance.saturating_sub(distributed));
+
+        Ok(())
+    }
+
+    fn set_circle_yield_strategy(env: Env, creator: Address, circle_id: u64, pool: Address) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        // The contract supplies straight to the pool, so only pools the admin has vetted may be chosen
+        if !is_approved_lending_pool(&env, &pool) {
+            return Err(SoroSusuError::InvalidAddress);
+        }
+
+        // Funds already supplied can only be recalled from the pool that holds them
+        if circle.yield_deposited > 0 {
+            return Err(SoroSusuError::InvalidOperation);
+        }
+
+        env.storage().instance().set(&DataKey::YieldStrategy(circle_id), &pool);
+
+        Ok(())
+    }
+
+    fn deposit_to_yield_pool(env: Env, caller: Address, circle_id: u64, amount: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+        if amount == 0 {
+            return Err(SoroSusuError::InvalidAmount);
+        }
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if caller != circle.creator && caller != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+        // Funds already in the pool have to be recalled with prepare_payout_liquidity first
+        if circle.yield_deposited != 0 {
+            return Err(SoroSusuError::InvalidOperation);
+        }
+
+        let lending_pool = circle_lending_pool(&env, circle_id)?;
+        let strategy: Option<Address> = env.storage().instance().get(&DataKey::YieldStrategy(circle_id));
+        if strategy.is_some_and(|pool| !is_approved_lending_pool(&env, &pool)) {
+            return Err(SoroSusuError::LendingPoolNotConfigured);
+        }
+
+        // Only this circle's own pots may go out; the rest of the balance belongs to other circles
+        if amount > circle_pot_balance(&env, &circle) {
+            return Err(SoroSusuError::InsufficientBalance);
+        }
+        let lending_client = LendingPoolClient::new(&env, &lending_pool);
+
+        // An upgraded pool may no longer match the ABI we call; keep the funds here until the admin re-vets it
+        let expected_version: u32 = env.storage().instance().get(&DataKey::LendingPoolVersion).unwrap_or(0);
+        if lending_client.get_lending_pool_version() != expected_version {
+            return Err(SoroSusuError::LendingPoolNotConfigured);
+        }
+
+        lending_client.supply(
+            &circle.token,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        circle.yield_deposited += amount;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn prepare_payout_liquidity(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if caller != circle.creator && caller != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        // A duration change whose notice period has passed applies from the next payout
+        let current_time = env.ledger().timestamp();
+        if circle.pending_cycle_duration != 0 && current_time >= circle.duration_change_effective_at {
+            circle.cycle_duration = circle.pending_cycle_duration;
+            circle.pending_cycle_duration = 0;
+            circle.duration_change_effective_at = 0;
+        }
+
+        if circle.yield_deposited != 0 {
+            withdraw_yield(&env, &mut circle)?;
+        }
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn get_yield_earnings(env: Env, circle_id: u64) -> Result<u64, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(circle.yield_earned_this_round)
+    }
+
+    fn is_yield_active(env: Env, circle_id: u64) -> Result<bool, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(circle.yield_deposited != 0)
+    }
+
+    fn get_insurance_status(env: Env, circle_id: u64) -> Result<(u64, bool, u32), SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok((insurance_fund(&env, circle_id), circle.is_insurance_used, circle.insurance_fee_bps))
+    }
+
+    fn get_insurance_fund(env: Env, circle_id: u64) -> Result<u64, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(insurance_fund(&env, circle_id))
+    }
+
+    fn trigger_insurance_coverage(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        // Only creator can trigger insurance
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        let member_info = check_insurance_trigger(&env, &circle, &member)?;
+        let member_contribution_amount = circle.contribution_amount * member_info.tier_multiplier as u64;
+
+        // Mark member as contributed in the bitmap
+        circle.contribution_bitmap |= 1 << member_info.index;
+        spend_insurance(&env, circle_id, member_contribution_amount);
+        circle.is_insurance_used = true;
+        record_insurance_claim(&env, circle_id, &member, member_contribution_amount);
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn can_trigger_insurance(env: Env, circle_id: u64, member: Address) -> Result<(bool, Symbol), SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let check = require_protocol_active(&env).and_then(|_| check_insurance_trigger(&env, &circle, &member));
+        let reason = match check {
+            Ok(_) => "ok",
+            Err(SoroSusuError::ProtocolPaused) => "protocol_paused",
+            Err(SoroSusuError::InsuranceAlreadyUsed) => "already_used",
+            Err(SoroSusuError::NotAMember) => "not_a_member",
+            Err(SoroSusuError::MemberNotActive) => "member_not_active",
+            Err(SoroSusuError::InsufficientBalance) => "insufficient_balance",
+            Err(SoroSusuError::SolvencyFloorBreached) => "solvency_floor_breached",
+            Err(SoroSusuError::AlreadyContributed) => "member_already_contributed",
+            Err(error) => return Err(error),
+        };
+        Ok((check.is_ok(), Symbol::new(&env, reason)))
+    }
+
+    fn get_insurance_claim_history(env: Env, circle_id: u64) -> Result<Vec<(u64, Address, u64)>, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(env.storage().instance().get(&DataKey::InsuranceClaimHistory(circle_id)).unwrap_or_else(|| Vec::new(&env)))
+    }
+
+    fn propose_penalty_change(env: Env, user: Address, circle_id: u64, new_bps: u32) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+        
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        
+        // Check if user is a member of this circle
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member_key = DataKey::Member(user.clone());
+        let member: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        if new_bps > 10000 {
+            return Err(SoroSusuError::InvalidBasisPoints);
+        }
+
+        require_governance_age(&env, &circle)?;
+
+        // A live vote cannot be wiped by re-proposing; an expired one is replaced along with its votes
+        let expiry_key = DataKey::ProposalExpiry(circle_id);
+        if circle.proposed_late_fee_bps != 0 {
+            let expiry: u64 = env.storage().instance().get(&expiry_key).unwrap_or(u64::MAX);
+            if env.ledger().timestamp() <= expiry {
+                return Err(SoroSusuError::ChangeAlreadyPending);
+            }
+            record_governance_outcome(&env, &circle, ProposalType::LateFee, circle.proposed_late_fee_bps, circle.proposal_votes_bitmap, false);
+        }
+        circle.proposed_late_fee_bps = new_bps;
+        circle.proposal_votes_bitmap = 0;
+        env.storage().instance().set(&expiry_key, &(env.ledger().timestamp() + PROPOSAL_EXPIRY_SECS));
+        
+        // Auto-vote for proposer
+        circle.proposal_votes_bitmap |= 1 << member.index;
+
+        // Check for immediate majority (e.g. 1 member circle)
+        if reaches_quorum(&env, circle_id, circle.proposal_votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS) {
+            record_governance_outcome(&env, &circle, ProposalType::LateFee, circle.proposed_late_fee_bps, circle.proposal_votes_bitmap, true);
+            circle.late_fee_bps = circle.proposed_late_fee_bps;
+            record_penalty_change(&env, circle_id, circle.late_fee_bps);
+            circle.proposed_late_fee_bps = 0;
+            circle.proposal_votes_bitmap = 0;
+            env.storage().instance().remove(&expiry_key);
+        }
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn propose_duration_change(env: Env, user: Address, circle_id: u64, new_duration: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        if new_duration == 0 {
+            return Err(SoroSusuError::InvalidCycleDuration);
+        }
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let protocol_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+
+        if user != circle.creator && user != protocol_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        require_governance_age(&env, &circle)?;
+
+        // Overwriting a change still in its notice period would cut that notice short
+        let current_time = env.ledger().timestamp();
+        if circle.pending_cycle_duration != 0 && current_time < circle.duration_change_effective_at {
+            return Err(SoroSusuError::ChangeAlreadyPending);
+        }
+
+        circle.pending_cycle_duration = new_duration;
+        circle.duration_change_effective_at = current_time + protocol_config(&env).duration_change_notice_secs;
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn set_contribution_amount(env: Env, caller: Address, circle_id: u64, new_amount: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        if new_amount < MIN_CONTRIBUTION_AMOUNT {
+            return Err(SoroSusuError::InvalidAmount);
+        }
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let protocol_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+
+        if caller != circle.creator && caller != protocol_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        // Same rule as duration changes: members always get the full notice
+        let current_time = env.ledger().timestamp();
+        let mut settings = circle_settings(&env, circle_id);
+        if settings.pending_contribution_amount != 0 && current_time < settings.contribution_effective_at {
+            return Err(SoroSusuError::ChangeAlreadyPending);
+        }
+
+        settings.pending_contribution_amount = new_amount;
+        settings.contribution_effective_at = current_time + protocol_config(&env).duration_change_notice_secs;
+
+        env.storage().instance().set(&DataKey::CircleSettings(circle_id), &settings);
+
+        Ok(())
+    }
+
+    fn cancel_duration_change(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let protocol_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+
+        if caller != circle.creator && caller != protocol_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        if circle.pending_cycle_duration == 0 {
+            return Err(SoroSusuError::NoActiveProposal);
+        }
+
+        circle.pending_cycle_duration = 0;
+        circle.duration_change_effective_at = 0;
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn vote_penalty_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        
+        // Check if user is a member of this circle
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member_key = DataKey::Member(user.clone());
+        let member: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        if circle.proposed_late_fee_bps == 0 {
+            return Err(SoroSusuError::NoActiveProposal);
+        }
+
+        let expiry_key = DataKey::ProposalExpiry(circle_id);
+        let expiry: u64 = env.storage().instance().get(&expiry_key).unwrap_or(u64::MAX);
+        if env.ledger().timestamp() > expiry {
+            return Err(SoroSusuError::ProposalExpired);
+        }
+
+        circle.proposal_votes_bitmap |= 1 << member.index;
+
+        if reaches_quorum(&env, circle_id, circle.proposal_votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS) {
+            record_governance_outcome(&env, &circle, ProposalType::LateFee, circle.proposed_late_fee_bps, circle.proposal_votes_bitmap, true);
+            circle.late_fee_bps = circle.proposed_late_fee_bps;
+            record_penalty_change(&env, circle_id, circle.late_fee_bps);
+            circle.proposed_late_fee_bps = 0;
+            circle.proposal_votes_bitmap = 0;
+            env.storage().instance().remove(&expiry_key);
+        }
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn admin_set_late_fee_bps(env: Env, caller: Address, circle_id: u64, new_bps: u32) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let protocol_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+
+        if caller != circle.creator && caller != protocol_admin {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        if new_bps > 10000 {
+            return Err(SoroSusuError::InvalidBasisPoints);
+        }
+
+        circle.late_fee_bps = new_bps;
+        record_penalty_change(&env, circle_id, new_bps);
+
+        // Drop any in-flight proposal so it cannot be voted in afterwards
+        if circle.proposed_late_fee_bps != 0 {
+            record_governance_outcome(&env, &circle, ProposalType::LateFee, circle.proposed_late_fee_bps, circle.proposal_votes_bitmap, false);
+        }
+        circle.proposed_late_fee_bps = 0;
+        circle.proposal_votes_bitmap = 0;
+        env.storage().instance().remove(&DataKey::ProposalExpiry(circle_id));
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn get_penalty_history(env: Env, circle_id: u64) -> Result<Vec<(u32, u64)>, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(env.storage().instance().get(&DataKey::PenaltyChangeHistory(circle_id)).unwrap_or_else(|| Vec::new(&env)))
+    }
+
+    fn get_governance_history(env: Env, circle_id: u64) -> Result<Vec<VoterTurnout>, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(env.storage().instance().get(&DataKey::GovernanceHistory(circle_id)).unwrap_or_else(|| Vec::new(&env)))
+    }
+
+    fn propose_contribution_change(env: Env, user: Address, circle_id: u64, new_amount: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        if new_amount == 0 {
+            return Err(SoroSusuError::InvalidAmount);
+        }
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        require_governance_age(&env, &circle)?;
+
+        // Replace any previous proposal along with its votes; the proposer votes for their own
+        circle.proposed_contribution_amount = new_amount;
+        circle.contribution_votes_bitmap = 1 << member.index;
+        env.storage().instance().set(
+            &StateKey::ContributionProposalExpiry(circle_id),
+            &(env.ledger().timestamp() + PROPOSAL_EXPIRY_SECS),
+        );
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn vote_contribution_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        if circle.proposed_contribution_amount == 0 {
+            return Err(SoroSusuError::NoActiveProposal);
+        }
+
+        let expiry: u64 = env.storage().instance().get(&StateKey::ContributionProposalExpiry(circle_id)).unwrap_or(u64::MAX);
+        if env.ledger().timestamp() > expiry {
+            return Err(SoroSusuError::ProposalExpired);
+        }
+
+        // Counted when the round is finalized, so deposits already made this round keep their amount
+        circle.contribution_votes_bitmap |= 1 << member.index;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn propose_insurance_fee_change(env: Env, user: Address, circle_id: u64, new_bps: u32) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        if new_bps > MAX_INSURANCE_FEE_BPS {
+            return Err(SoroSusuError::InvalidBasisPoints);
+        }
+
+        require_governance_age(&env, &circle)?;
+
+        // Replace any previous proposal; the proposer votes for their own
+        if circle.insurance_fee_votes_bitmap != 0 {
+            record_governance_outcome(&env, &circle, ProposalType::InsuranceFee, circle.proposed_insurance_fee_bps, circle.insurance_fee_votes_bitmap, false);
+        }
+        circle.proposed_insurance_fee_bps = new_bps;
+        circle.insurance_fee_votes_bitmap = 1 << member.index;
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn vote_insurance_fee_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        if circle.insurance_fee_votes_bitmap == 0 {
+            return Err(SoroSusuError::NoActiveProposal);
+        }
+
+        // Counted when the round is finalized, so deposits already made this round keep their fee
+        circle.insurance_fee_votes_bitmap |= 1 << member.index;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn propose_deadline_extension(env: Env, user: Address, circle_id: u64, extension_secs: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        if extension_secs == 0 || extension_secs > MAX_DEADLINE_EXTENSION_SECS {
+            return Err(SoroSusuError::InvalidAmount);
+        }
+
+        let request_key = DataKey::ExtensionRequest(circle_id);
+        if env.storage().instance().has(&request_key) {
+            return Err(SoroSusuError::ChangeAlreadyPending);
+        }
+        if env.storage().instance().has(&StateKey::DeadlineExtended(circle_id)) {
+            return Err(SoroSusuError::InvalidOperation);
+        }
+
+        let request = ExtensionRequest {
+            extension_secs,
+            votes_bitmap: 1 << member.index,
+        };
+
+        // Check for immediate majority (e.g. 1 member circle)
+        if reaches_quorum(&env, circle_id, request.votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS) {
+            circle.deadline_timestamp += extension_secs;
+            env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+            env.storage().instance().set(&StateKey::DeadlineExtended(circle_id), &true);
+        } else {
+            env.storage().instance().set(&request_key, &request);
+        }
+
+        Ok(())
+    }
+
+    fn vote_deadline_extension(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        let request_key = DataKey::ExtensionRequest(circle_id);
+        let mut request: ExtensionRequest = env.storage().instance().get(&request_key).ok_or(SoroSusuError::NoActiveProposal)?;
+        request.votes_bitmap |= 1 << member.index;
+
+        if reaches_quorum(&env, circle_id, request.votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS) {
+            circle.deadline_timestamp += request.extension_secs;
+            env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+            env.storage().instance().remove(&request_key);
+            env.storage().instance().set(&StateKey::DeadlineExtended(circle_id), &true);
+        } else {
+            env.storage().instance().set(&request_key, &request);
+        }
+
+        Ok(())
+    }
+
+    fn propose_max_members_change(env: Env, user: Address, circle_id: u64, new_max: u32) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        if new_max > 64 {
+            return Err(SoroSusuError::MaxMembersExceeded);
+        }
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        // Shrinking below the current roster would orphan existing members
+        if new_max < circle.member_count {
+            return Err(SoroSusuError::MaxMembersBelowMemberCount);
+        }
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        // Set proposal and auto-vote for proposer
+        circle.proposed_max_members = new_max;
+        circle.max_members_votes_bitmap = 1 << member.index;
+
+        if reaches_quorum(&env, circle_id, circle.max_members_votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS) {
+            apply_max_members_change(&mut circle)?;
+        }
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn vote_max_members_change(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        if circle.proposed_max_members == 0 {
+            return Err(SoroSusuError::NoActiveProposal);
+        }
+
+        circle.max_members_votes_bitmap |= 1 << member.index;
+
+        if reaches_quorum(&env, circle_id, circle.max_members_votes_bitmap.count_ones(), circle.member_count, MIN_QUORUM_BPS) {
+            apply_max_members_change(&mut circle)?;
+        }
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn propose_address_change(env: Env, user: Address, circle_id: u64, old_address: Address, new_address: Address) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        if old_address == new_address {
+            return Err(SoroSusuError::InvalidAddress);
+        }
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let old_member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(old_address.clone()));
+        if old_member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let old_member_key = DataKey::Member(old_address.clone());
+        let old_member: Member = env.storage().instance().get(&old_member_key).ok_or(SoroSusuError::NotAMember)?;
+        if old_member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        // The member's emergency contact speaks for the lost key, so the proposal carries that seat's vote
+        let proposer_index = if old_member.emergency_contact == Some(user.clone()) {
+            old_member.index
+        } else {
+            let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+            if member_circle != Some(circle_id) {
+                return Err(SoroSusuError::NotAMember);
+            }
+            let proposer_key = DataKey::Member(user.clone());
+            let proposer: Member = env.storage().instance().get(&proposer_key).ok_or(SoroSusuError::NotAMember)?;
+            if proposer.status != MemberStatus::Active {
+                return Err(SoroSusuError::MemberNotActive);
+            }
+
+            // Members must sit through a full cycle before they can move someone else's seat
+            let joined_at: u64 = env.storage().instance().get(&DataKey::MemberJoinTime(user.clone())).unwrap_or(0);
+            if env.ledger().timestamp() < joined_at + circle.cycle_duration {
+                return Err(SoroSusuError::Unauthorized);
+            }
+            proposer.index
+        };
+
+        let new_member_key = DataKey::Member(new_address.clone());
+        if env.storage().instance().has(&new_member_key) {
+            return Err(SoroSusuError::AlreadyMember);
+        }
+
+        // A circle has one recovery vote at a time; a live one cannot be replaced and its votes wiped
+        let existing: Option<RecoveryProposal> = env.storage().instance().get(&DataKey::RecoveryProposal(circle_id));
+        if existing.is_some_and(|existing| !recovery_expired(&env, &existing)) {
+            return Err(SoroSusuError::ChangeAlreadyPending);
+        }
+
+        let proposal = RecoveryProposal {
+            old_address,
+            new_address,
+            votes_bitmap: 1 << proposer_index,
+            proposed_at: env.ledger().timestamp(),
+        };
+
+        apply_recovery_if_consensus(&env, &mut circle, &proposal)?;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn vote_for_recovery(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let mut proposal: RecoveryProposal = env.storage().instance()
+            .get(&DataKey::RecoveryProposal(circle_id))
+            .ok_or(SoroSusuError::NoActiveProposal)?;
+        if recovery_expired(&env, &proposal) {
+            return Err(SoroSusuError::ProposalExpired);
+        }
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member_key = DataKey::Member(user.clone());
+        let member: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        proposal.votes_bitmap |= 1 << member.index;
+        apply_recovery_if_consensus(&env, &mut circle, &proposal)?;
+
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn cancel_expired_recovery(env: Env, circle_id: u64) -> Result<(), SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+
+        let proposal_key = DataKey::RecoveryProposal(circle_id);
+        let proposal: RecoveryProposal = env.storage().instance().get(&proposal_key).ok_or(SoroSusuError::NoActiveProposal)?;
+        if !recovery_expired(&env, &proposal) {
+            return Err(SoroSusuError::OperationNotExpired);
+        }
+
+        env.storage().instance().remove(&proposal_key);
+        env.events().publish(
+            (Symbol::new(&env, "RECOVERY_EXPIRED"), circle_id),
+            (proposal.old_address, proposal.new_address),
+        );
+
+        Ok(())
+    }
+
+    fn set_emergency_contact(env: Env, member: Address, contact: Address) -> Result<(), SoroSusuError> {
+        member.require_auth();
+        require_protocol_active(&env)?;
+
+        if contact == member {
+            return Err(SoroSusuError::InvalidAddress);
+        }
+
+        let member_key = DataKey::Member(member);
+        let mut member_info: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+        if member_info.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        member_info.emergency_contact = Some(contact);
+        env.storage().instance().set(&member_key, &member_info);
+
+        Ok(())
+    }
+
+    fn get_emergency_contact(env: Env, member: Address) -> Result<Option<Address>, SoroSusuError> {
+        let member: Member = env.storage().instance().get(&DataKey::Member(member)).ok_or(SoroSusuError::NotAMember)?;
+        Ok(member.emergency_contact)
+    }
+
+    fn reclaim_nft(env: Env, old_address: Address, new_address: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        old_address.require_auth();
+        require_protocol_active(&env)?;
+        new_address.require_auth();
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        if old_address == new_address {
+            return Err(SoroSusuError::InvalidAddress);
+        }
+        if is_blocked(&env, &new_address) {
+            return Err(SoroSusuError::AddressBlocked);
+        }
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(old_address.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+
+        let member = move_member(&env, &mut circle, &old_address, &new_address)?;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        let token_id = encode_nft_token_id(circle_id, member.index);
+        let nft_client = SusuNftClient::new(&env, &circle.nft_contract);
+        nft_client.burn(&old_address, &token_id);
+        nft_client.mint(&new_address, &token_id);
+
+        env.events().publish(
+            (Symbol::new(&env, "NFT_RECLAIMED"), circle_id),
+            (old_address, new_address),
+        );
+
+        Ok(())
+    }
+
+    fn set_refund_ejected_members(env: Env, caller: Address, circle_id: u64, enabled: bool) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        circle.refund_ejected_members = enabled;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn set_priority_ordering(env: Env, caller: Address, circle_id: u64, enabled: bool) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        circle.priority_ordering = enabled;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
+    }
+
+    fn set_quorum_threshold(env: Env, caller: Address, circle_id: u64, quorum_threshold_bps: u32) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        if !(MIN_QUORUM_BPS..=10000).contains(&quorum_threshold_bps) {
+            return Err(SoroSusuError::InvalidBasisPoints);
+        }
+
+        // Members join under the rules they were shown
+        if circle.member_count > 0 {
+            return Err(SoroSusuError::InvalidOperation);
+        }
+
+        let mut settings = circle_settings(&env, circle_id);
+        settings.quorum_threshold_bps = quorum_threshold_bps;
+        env.storage().instance().set(&DataKey::CircleSettings(circle_id), &settings);
+
+        Ok(())
     }
-}
 
-// Execute trigger insurance operation
-fn execute_trigger_insurance(env: &Env, operation: &AdminOperation) {
-    let circle_id = operation.circle_id;
-    let target_member = operation
-        .target_member
-        .clone()
-        .unwrap_or_else(|| panic!("No target member"));
-    
-    let mut circle: CircleInfo = env.storage().instance()
-        .get(&DataKey::Circle(circle_id))
-        .unwrap_or_else(|| panic!("Circle not found"));
+    fn update_yield_idle_threshold(env: Env, creator: Address, circle_id: u64, new_secs: u64) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
 
-    // Get member info first
-    let member_key = DataKey::Member(target_member.clone());
-    let member_info: Member = env.storage().instance()
-        .get(&member_key)
-        .unwrap_or_else(|| panic!("Member not found"));
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
 
-    if !member_info.is_active {
-        panic!("Member is ejected");
-    }
+        if new_secs < protocol_config(&env).yield_liquidity_buffer_secs || new_secs > circle.cycle_duration {
+            return Err(SoroSusuError::InvalidCycleDuration);
+        }
 
-    // Check if insurance was already used this cycle
-    if circle.is_insurance_used {
-        panic!("Insurance already used this cycle");
+        let mut settings = circle_settings(&env, circle_id);
+        settings.max_yield_idle_secs = new_secs;
+        env.storage().instance().set(&DataKey::CircleSettings(circle_id), &settings);
+
+        Ok(())
     }
 
-    // Check if there is enough balance
-    let member_contribution_amount = circle.contribution_amount * member_info.tier_multiplier as u64;
-    if circle.insurance_balance < member_contribution_amount {
-        panic!("Insufficient insurance balance");
+    fn set_nft_contract(env: Env, caller: Address, circle_id: u64, new_nft_contract: Address) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        circle.nft_contract = new_nft_contract.clone();
+        circle.nft_contract_version += 1;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        env.events().publish(
+            (Symbol::new(&env, "NFT_CONTRACT_UPDATED"), circle_id),
+            (new_nft_contract, circle.nft_contract_version),
+        );
+
+        Ok(())
     }
 
-    // Mark member as contributed in the bitmap
-    if (circle.contribution_bitmap & (1 << member_info.index)) != 0 {
-        panic!("Member already contributed");
+    fn set_accepted_tokens(env: Env, caller: Address, circle_id: u64, tokens: Vec<Address>) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+        if !tokens.contains(&circle.token) {
+            return Err(SoroSusuError::TokenNotAccepted);
+        }
+
+        circle.accepted_tokens = tokens;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        Ok(())
     }
 
-    circle.contribution_bitmap |= 1 << member_info.index;
-    circle.insurance_balance -= member_contribution_amount;
-    circle.is_insurance_used = true;
-    
-    // Track the insurance contribution for current round
-    let contribution_key = DataKey::CurrentRoundContribution(circle_id, member_info.index);
-    env.storage().instance().set(&contribution_key, &member_contribution_amount);
+    fn set_deposit_token(env: Env, user: Address, circle_id: u64, token: Address) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
 
-    env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
-}
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let user_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if user_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        if !circle.accepted_tokens.contains(&token) {
+            return Err(SoroSusuError::TokenNotAccepted);
+        }
 
-#[contractclient(name = "SusuNftClient")]
-pub trait SusuNftTrait {
-    fn mint(env: Env, to: Address, token_id: u128);
-    fn burn(env: Env, from: Address, token_id: u128);
-}
+        env.storage().instance().set(&DataKey::MemberToken(user), &token);
 
-#[contractclient(name = "LendingPoolClient")]
-pub trait LendingPoolTrait {
-    fn supply(env: Env, token: Address, from: Address, amount: u64);
-    fn withdraw(env: Env, token: Address, to: Address, amount: u64);
-}
+        Ok(())
+    }
 
-// --- IMPLEMENTATION ---
+    fn get_payout_order(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let mut order = Vec::new(&env);
 
-#[contract]
-pub struct SoroSusu;
+        if circle.priority_ordering {
+            // Replay the trust-score selection against a scratch copy of the payout bitmap
+            let mut remaining = circle.clone();
+            while let Ok((index, address)) = select_next_recipient(&env, &remaining) {
+                order.push_back(address);
+                remaining.payout_bitmap |= 1 << index;
+            }
+            return Ok(order);
+        }
 
-#[contractimpl]
-impl SoroSusuTrait for SoroSusu {
-    fn init(env: Env, admin: Address) {
-        // Initialize the circle counter to 0 if it doesn't exist
-        if !env.storage().instance().has(&DataKey::CircleCount) {
-            env.storage().instance().set(&DataKey::CircleCount, &0u64);
+        if circle.member_count == 0 {
+            return Ok(order);
         }
-        // Set the admin
-        env.storage().instance().set(&DataKey::Admin, &admin);
-    }
 
-    fn set_lending_pool(env: Env, admin: Address, pool: Address) {
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not initialized");
-        if admin != stored_admin {
-            panic!("Unauthorized");
+        let start = circle.current_recipient_index % circle.member_count;
+        for offset in 0..circle.member_count {
+            let index = (start + offset) % circle.member_count;
+            if circle.payout_bitmap & (1 << index) != 0 {
+                continue;
+            }
+            if let Some(address) = get_member_address_by_index(&circle, index) {
+                order.push_back(address);
+            }
         }
 
-        env.storage().instance().set(&DataKey::LendingPool, &pool);
+        Ok(order)
     }
 
-    fn create_circle(env: Env, creator: Address, amount: u64, max_members: u32, token: Address, cycle_duration: u64, insurance_fee_bps: u32, nft_contract: Address) -> u64 {
-        // 1. Get the current Circle Count
-        let mut circle_count: u64 = env.storage().instance().get(&DataKey::CircleCount).unwrap_or(0);
-        
-        // 2. Increment the ID for the new circle
-        circle_count += 1;
+    fn get_round_contributors(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(members_by_contribution(&env, &circle, true))
+    }
 
-        if max_members > 64 {
-            panic!("Max members cannot exceed 64 for optimization");
+    fn get_round_defaulters(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(members_by_contribution(&env, &circle, false))
+    }
+
+    fn get_round_contribution_summary(env: Env, circle_id: u64) -> Result<Vec<(Address, u64, bool)>, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+
+        let mut summary = Vec::new(&env);
+        for i in 0..circle.member_count {
+            let member_address = circle.member_addresses.get(i).unwrap();
+            let amount: u64 = env.storage().instance().get(&DataKey::CurrentRoundContribution(circle_id, i)).unwrap_or(0);
+            let has_contributed = (circle.contribution_bitmap >> i) & 1 == 1;
+            summary.push_back((member_address, amount, has_contributed));
         }
+        Ok(summary)
+    }
+
+    fn get_pending_exits(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
 
-        if insurance_fee_bps > 10000 {
-            panic!("Insurance fee cannot exceed 100%");
+        let mut pending = Vec::new(&env);
+        for member_address in circle.member_addresses.iter() {
+            let member: Option<Member> = env.storage().instance().get(&DataKey::Member(member_address.clone()));
+            if member.is_some_and(|member| member.status == MemberStatus::AwaitingReplacement) {
+                pending.push_back(member_address);
+            }
         }
+        Ok(pending)
+    }
 
-        // 3. Create the Circle Data Struct
-        let current_time = env.ledger().timestamp();
-        let new_circle = CircleInfo {
-            id: circle_count,
-            creator: creator.clone(),
-            contribution_amount: amount,
-            max_members,
-            member_count: 0,
-            current_recipient_index: 0,
-            is_active: true,
-            token,
-            deadline_timestamp: current_time + cycle_duration,
-            cycle_duration,
-            pending_cycle_duration: 0,
-            duration_change_effective_at: 0,
-            contribution_bitmap: 0,
-            payout_bitmap: 0,
-            insurance_balance: 0,
-            insurance_fee_bps,
-            is_insurance_used: false,
-            late_fee_bps: 100, // Default 1%
-            proposed_late_fee_bps: 0,
-            proposal_votes_bitmap: 0,
-            nft_contract,
-            is_round_finalized: false,
-            current_pot_recipient: creator.clone(), // Initialize with creator
-            member_addresses: Vec::new(&env), // Initialize empty member addresses vector
-            yield_deposited: 0,
-            recovery_old_address: None,
-            recovery_new_address: None,
-            recovery_votes_bitmap: 0,
-        };
+    fn get_round_number(env: Env, circle_id: u64) -> Result<u32, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(env.storage().instance().get(&DataKey::RoundNumber(circle_id)).unwrap_or(0))
+    }
 
-        // 4. Save the Circle and the new Count
-        env.storage().instance().set(&DataKey::Circle(circle_count), &new_circle);
-        env.storage().instance().set(&DataKey::CircleCount, &circle_count);
+    fn get_circle_stats(env: Env, circle_id: u64) -> Result<CircleStats, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(circle_stats(&env, circle_id))
+    }
 
-        // 5. Initialize Group Reserve if not exists
-        if !env.storage().instance().has(&DataKey::GroupReserve) {
-            env.storage().instance().set(&DataKey::GroupReserve, &0u64);
-        }
+    fn get_circle_age(env: Env, circle_id: u64) -> Result<u64, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(env.ledger().timestamp().saturating_sub(circle_settings(&env, circle_id).created_at))
+    }
 
-        // 6. Return the new ID
-        circle_count
+    fn get_last_finalized_at(env: Env, circle_id: u64) -> Result<Option<u64>, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(env.storage().instance().get(&DataKey::LastRoundFinalizedAt(circle_id)))
     }
 
-    fn join_circle(env: Env, user: Address, circle_id: u64, tier_multiplier: u32) {
-        Self::join_circle_with_referrer(env, user, circle_id, tier_multiplier, None);
+    fn freeze_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(member.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+
+        let member_key = DataKey::Member(member);
+        let mut member_info: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+        if member_info.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        member_info.status = MemberStatus::Frozen;
+        member_info.frozen_rounds = 0;
+        env.storage().instance().set(&member_key, &member_info);
+
+        Ok(())
     }
 
-    fn join_circle_with_referrer(env: Env, user: Address, circle_id: u64, tier_multiplier: u32, referrer: Option<Address>) {
-        // 1. Authorization: The user MUST sign this transaction
-        user.require_auth();
+    fn unfreeze_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
 
-        // 2. Retrieve the circle data
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
 
-        // 3. Check if the circle is full
-        if circle.member_count >= circle.max_members {
-            panic!("Circle is full");
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(member.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
         }
 
-        // 4. Check if user is already a member to prevent duplicates
-        let member_key = DataKey::Member(user.clone());
-        if env.storage().instance().has(&member_key) {
-            panic!("User is already a member");
+        let member_key = DataKey::Member(member);
+        let mut member_info: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+        if member_info.status != MemberStatus::Frozen {
+            return Err(SoroSusuError::MemberNotFrozen);
         }
 
-        // 5. Validate tier_multiplier (must be at least 1)
-        if tier_multiplier == 0 {
-            panic!("Tier multiplier must be at least 1");
+        member_info.status = MemberStatus::Active;
+        member_info.frozen_rounds = 0;
+        env.storage().instance().set(&member_key, &member_info);
+
+        Ok(())
+    }
+
+    fn sweep_inactive_members(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
+
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
         }
 
-        if let Some(referrer_address) = referrer.clone() {
-            if referrer_address == user {
-                panic!("Referrer cannot be the same as user");
+        let nft_client = SusuNftClient::new(&env, &circle.nft_contract);
+        let mut new_index = [u32::MAX; 64];
+        let mut survivors = Vec::new(&env);
+        let mut next_recipient_index = 0u32;
+        for old_index in 0..circle.member_count {
+            let member_address = circle.member_addresses.get(old_index).ok_or(SoroSusuError::InvalidRecipientIndex)?;
+            let member_key = DataKey::Member(member_address.clone());
+            let mut member: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+
+            let old_contribution_key = DataKey::CurrentRoundContribution(circle_id, old_index);
+            let contribution: Option<u64> = env.storage().instance().get(&old_contribution_key);
+            env.storage().instance().remove(&old_contribution_key);
+            if member.status == MemberStatus::Ejected {
+                continue;
+            }
+
+            let index = survivors.len();
+            new_index[old_index as usize] = index;
+            if old_index < circle.current_recipient_index {
+                next_recipient_index += 1;
+            }
+            if let Some(amount) = contribution {
+                env.storage().instance().set(&DataKey::CurrentRoundContribution(circle_id, index), &amount);
             }
-            let referrer_key = DataKey::Member(referrer_address);
-            if !env.storage().instance().has(&referrer_key) {
-                panic!("Referrer must already be a member");
+            if index != old_index {
+                member.index = index;
+                env.storage().instance().set(&member_key, &member);
+
+                // The NFT id encodes the seat, so the token is reissued under the new one
+                nft_client.burn(&member_address, &encode_nft_token_id(circle_id, old_index));
+                nft_client.mint(&member_address, &encode_nft_token_id(circle_id, index));
             }
+            survivors.push_back(member_address);
         }
 
-        // 6. Create and store the new member
-        let new_member = Member {
-            address: user.clone(),
-            index: circle.member_count as u32,
-            contribution_count: 0,
-            last_contribution_time: 0,
-            is_active: true,
-            tier_multiplier,
-            status: MemberStatus::Active,
-            total_contributed: 0,
-            referrer,
-        };
+        let removed = circle.member_count - survivors.len();
+        if removed == 0 {
+            return Ok(());
+        }
+
+        circle.contribution_bitmap = compact_bitmap(circle.contribution_bitmap, &new_index);
+        circle.late_contribution_bitmap = compact_bitmap(circle.late_contribution_bitmap, &new_index);
+        circle.payout_bitmap = compact_bitmap(circle.payout_bitmap, &new_index);
+        circle.proposal_votes_bitmap = compact_bitmap(circle.proposal_votes_bitmap, &new_index);
+        circle.contribution_votes_bitmap = compact_bitmap(circle.contribution_votes_bitmap, &new_index);
+        circle.max_members_votes_bitmap = compact_bitmap(circle.max_members_votes_bitmap, &new_index);
+        circle.insurance_fee_votes_bitmap = compact_bitmap(circle.insurance_fee_votes_bitmap, &new_index);
+        let on_time_key = StateKey::OnTimePayers(circle_id);
+        if let Some(on_time_bitmap) = env.storage().instance().get::<StateKey, u64>(&on_time_key) {
+            env.storage().instance().set(&on_time_key, &compact_bitmap(on_time_bitmap, &new_index));
+        }
+        let recovery_key = DataKey::RecoveryProposal(circle_id);
+        if let Some(mut proposal) = env.storage().instance().get::<DataKey, RecoveryProposal>(&recovery_key) {
+            proposal.votes_bitmap = compact_bitmap(proposal.votes_bitmap, &new_index);
+            env.storage().instance().set(&recovery_key, &proposal);
+        }
+
+        // Rotation resumes at the first surviving seat at or after the one that was next
+        circle.current_recipient_index = next_recipient_index;
+        circle.member_addresses = survivors;
+        circle.member_count -= removed;
+        env.storage().instance().set(&DataKey::CircleMemberCount(circle_id), &circle.member_count);
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        env.events().publish((Symbol::new(&env, "MEMBERS_SWEPT"), circle_id), removed);
+
+        Ok(())
+    }
+
+    fn eject_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
         
-        // 7. Store the member and update circle count
-        env.storage().instance().set(&member_key, &new_member);
-        circle.member_addresses.push_back(user.clone());
-        circle.member_count += 1;
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
         
-        // 8. Save the updated circle back to storage
+        // Only creator can eject
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(member.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member_key = DataKey::Member(member.clone());
+        let mut member_info: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+
+        if member_info.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        refund_ejected_member(&env, &mut circle, &member_info);
         env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
 
-        // 9. Mint Participation NFT
-        // Token ID = (CircleID << 64) | MemberIndex
-        let token_id = (circle_id as u128) << 64 | (new_member.index as u128);
+        // Mark as ejected
+        member_info.status = MemberStatus::Ejected;
+        update_circle_stats(&env, circle_id, |stats| stats.total_members_ejected += 1);
+        env.storage().instance().set(&member_key, &member_info);
+        env.storage().instance().set(&DataKey::EjectionTime(member.clone()), &env.ledger().timestamp());
+
+        // Burn NFT
+        let token_id = encode_nft_token_id(circle_id, member_info.index);
         let client = SusuNftClient::new(&env, &circle.nft_contract);
-        client.mint(&user, &token_id);
+        client.burn(&member, &token_id);
+
+        Ok(())
     }
 
-    fn deposit(env: Env, user: Address, circle_id: u64) {
-        // 1. Authorization: The user must sign this!
+    fn request_exit(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
         user.require_auth();
+        require_protocol_active(&env)?;
 
-        // 2. Load the Circle Data
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        let current_time = env.ledger().timestamp();
-
-        // Keep pot liquid before deadline by recalling supplied funds.
-        if circle.yield_deposited > 0 && current_time + YIELD_LIQUIDITY_BUFFER_SECS >= circle.deadline_timestamp {
-            let lending_pool: Address = env.storage().instance().get(&DataKey::LendingPool)
-                .unwrap_or_else(|| panic!("Lending pool not configured"));
-            let lending_client = LendingPoolClient::new(&env, &lending_pool);
-            lending_client.withdraw(
-                &circle.token,
-                &env.current_contract_address(),
-                &circle.yield_deposited,
-            );
-            circle.yield_deposited = 0;
+        // Make sure the circle exists before touching member state
+        if !env.storage().instance().has(&DataKey::Circle(circle_id)) {
+            return Err(SoroSusuError::CircleNotFound);
         }
 
-        // 3. Check if user is actually a member
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
         let member_key = DataKey::Member(user.clone());
         let mut member: Member = env.storage().instance().get(&member_key)
-            .unwrap_or_else(|| panic!("User is not a member of this circle"));
+            .ok_or(SoroSusuError::NotAMember)?;
 
+        // Check if member is active and can request exit
         if member.status != MemberStatus::Active {
-            panic!("Member is not active");
+            return Err(SoroSusuError::MemberNotActive);
         }
 
-        // 4. Create the Token Client
-        let client = token::Client::new(&env, &circle.token);
+        // Change member status to AwaitingReplacement
+        member.status = MemberStatus::AwaitingReplacement;
+        env.storage().instance().set(&member_key, &member);
 
-        // 5. Check if payment is late and apply penalty if needed
-        let current_time = env.ledger().timestamp();
-        let mut penalty_amount = 0u64;
-        
-        // Calculate member's contribution amount based on tier
-        let member_contribution_amount = circle.contribution_amount * member.tier_multiplier as u64;
+        // Store the pending exit request
+        let pending_exit_key = DataKey::PendingExit(circle_id, user.clone());
+        env.storage().instance().set(&pending_exit_key, &true);
 
-        if current_time > circle.deadline_timestamp {
-            // Calculate penalty based on dynamic rate and member's tier
-            let base_penalty = (member_contribution_amount * circle.late_fee_bps as u64) / 10000;
-            penalty_amount = apply_referral_discount(&env, &circle, &member, base_penalty);
-            
-            // Update Group Reserve balance
-            let mut reserve_balance: u64 = env.storage().instance().get(&DataKey::GroupReserve).unwrap_or(0);
-            reserve_balance += penalty_amount;
-            env.storage().instance().set(&DataKey::GroupReserve, &reserve_balance);
+        // Note: We keep the member's position in the queue locked until fill_vacancy is called
+
+        Ok(())
+    }
+
+    fn cancel_exit_request(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+        require_circle_exists(&env, circle_id)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+
+        let pending_exit_key = DataKey::PendingExit(circle_id, user.clone());
+        if !env.storage().instance().has(&pending_exit_key) {
+            return Err(SoroSusuError::NoPendingExit);
+        }
+
+        let member_key = DataKey::Member(user.clone());
+        let mut member: Member = env.storage().instance().get(&member_key)
+            .ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::AwaitingReplacement {
+            return Err(SoroSusuError::MemberNotActive);
+        }
+
+        // Exits forced by a long freeze are not the member's to take back
+        if member.frozen_rounds >= FROZEN_ROUNDS_BEFORE_EXIT {
+            return Err(SoroSusuError::Unauthorized);
+        }
+
+        member.status = MemberStatus::Active;
+        env.storage().instance().set(&member_key, &member);
+        env.storage().instance().remove(&pending_exit_key);
+
+        env.events().publish(
+            (Symbol::new(&env, "EXIT_CANCELLED"), circle_id),
+            user,
+        );
+
+        Ok(())
+    }
+
+    fn fill_vacancy(env: Env, new_member: Address, circle_id: u64, exiting_member_address: Address) -> Result<(), SoroSusuError> {
+        new_member.require_auth();
+        require_protocol_active(&env)?;
+
+        if is_blocked(&env, &new_member) {
+            return Err(SoroSusuError::AddressBlocked);
+        }
+
+        // Get the circle information
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id))
+            .ok_or(SoroSusuError::CircleNotFound)?;
+
+        // Verify there's a pending exit for the specified member
+        let pending_exit_key = DataKey::PendingExit(circle_id, exiting_member_address.clone());
+        if !env.storage().instance().has(&pending_exit_key) {
+            return Err(SoroSusuError::NoPendingExit);
+        }
+
+        // The seat being filled has to be in this circle
+        let exiting_member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(exiting_member_address.clone()));
+        if exiting_member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+
+        // Get the exiting member's information
+        let exiting_member_key = DataKey::Member(exiting_member_address.clone());
+        let exiting_member: Member = env.storage().instance().get(&exiting_member_key)
+            .ok_or(SoroSusuError::NotAMember)?;
+
+        if exiting_member.status != MemberStatus::AwaitingReplacement {
+            return Err(SoroSusuError::NoPendingExit);
+        }
+
+        // Check if new member is already in any circle
+        let new_member_key = DataKey::Member(new_member.clone());
+        if env.storage().instance().has(&new_member_key) {
+            return Err(SoroSusuError::AlreadyMember);
         }
 
-        // 6. Calculate Insurance Fee and Transfer the full amount from user
-        let insurance_fee = ((member_contribution_amount as u128 * circle.insurance_fee_bps as u128) / 10000) as u64;
-        let total_amount = member_contribution_amount + insurance_fee;
+        // Refund the principal paid in, tier multiplier included (insurance fees are not refunded). A deposit for
+        // the open round leaves with them, so the replacement pays that round for the seat.
+        refund_contributions(&env, &mut circle, &exiting_member);
 
-        let total_amount_i128 = total_amount as i128;
-        client.transfer(
-            &user, 
-            &env.current_contract_address(), 
-            &total_amount_i128
-        );
+        // Create new member with the same index as the exiting member
+        let replacement_member = Member {
+            address: new_member.clone(),
+            index: exiting_member.index, // Inherit the position in queue
+            contribution_count: 0,
+            last_contribution_time: 0,
+            tier_multiplier: 1,
+            status: MemberStatus::Active,
+            total_contributed: 0,
+            referrer: None,
+            trust_score: 0,
+            frozen_rounds: 0,
+            emergency_contact: None,
+            late_contributions: 0,
+        };
 
-        if insurance_fee > 0 {
-            circle.insurance_balance += insurance_fee;
+        // Store the new member
+        env.storage().instance().set(&new_member_key, &replacement_member);
+        env.storage().instance().set(&DataKey::MemberCircle(new_member.clone()), &circle_id);
+        env.storage().instance().remove(&DataKey::MemberCircle(exiting_member_address.clone()));
+        env.storage().instance().set(&DataKey::MemberJoinTime(new_member.clone()), &env.ledger().timestamp());
+        update_circle_stats(&env, circle_id, |stats| stats.total_members_joined += 1);
+        record_join(&env, circle_id, &new_member);
+
+        // The replacement takes over the seat, and with it the exiting member's turn for the pot
+        circle.member_addresses.set(exiting_member.index, new_member.clone());
+        if circle.current_pot_recipient == exiting_member_address {
+            circle.current_pot_recipient = new_member.clone();
         }
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
 
-        // 7. Update member contribution info
-        member.contribution_count += 1;
-        member.last_contribution_time = current_time;
-        member.total_contributed += circle.contribution_amount;
-        
-        // 8. Save updated member info
-        env.storage().instance().set(&member_key, &member);
-
-        // 9. Track individual contribution for current round
-        let contribution_key = DataKey::CurrentRoundContribution(circle_id, member.index);
-        env.storage().instance().set(&contribution_key, &member_contribution_amount);
+        // Update exiting member status to Ejected (effectively removed)
+        let mut updated_exiting_member = exiting_member.clone();
+        updated_exiting_member.status = MemberStatus::Ejected;
+        env.storage().instance().set(&exiting_member_key, &updated_exiting_member);
+        env.storage().instance().set(&DataKey::EjectionTime(exiting_member_address.clone()), &env.ledger().timestamp());
 
-        // 10. Update circle deadline for next cycle
-        circle.deadline_timestamp = current_time + circle.cycle_duration;
-        circle.contribution_bitmap |= 1 << member.index;
+        // Remove the pending exit record
+        env.storage().instance().remove(&pending_exit_key);
 
-        // Emit a health snapshot for indexers/frontends.
-        let active_members = circle.member_count as u32;
-        let contributed_members = core::cmp::min(circle.contribution_bitmap.count_ones(), active_members);
-        let missed_payments = active_members.saturating_sub(contributed_members);
-        let trust_score = if active_members == 0 {
-            0
-        } else {
-            (contributed_members * 100) / active_members
-        };
+        // Burn the exiting member's NFT
+        let token_id = encode_nft_token_id(circle_id, exiting_member.index);
+        let nft_client = SusuNftClient::new(&env, &circle.nft_contract);
+        nft_client.burn(&exiting_member_address, &token_id);
 
-        let health_update = GroupHealthUpdateEvent {
-            group_id: circle_id,
-            missed_payments,
-            active_members,
-            trust_score,
-        };
-        env.events()
-            .publish((Symbol::new(&env, "GROUP_HEALTH"), circle_id), health_update);
+        // Mint new NFT for the replacement member
+        nft_client.mint(&new_member, &token_id);
 
-        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+        Ok(())
     }
 
-    fn deposit_to_yield_pool(env: Env, caller: Address, circle_id: u64, amount: u64) {
-        caller.require_auth();
-        if amount == 0 {
-            panic!("Amount must be greater than zero");
+    fn request_tier_change(env: Env, user: Address, circle_id: u64, new_tier: u32) -> Result<(), SoroSusuError> {
+        user.require_auth();
+        require_protocol_active(&env)?;
+        require_circle_exists(&env, circle_id)?;
+
+        let member_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(user.clone()));
+        if member_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(user.clone())).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
         }
 
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not initialized");
-        if caller != circle.creator && caller != stored_admin {
-            panic!("Unauthorized");
+        if new_tier == 0 || new_tier == member.tier_multiplier {
+            return Err(SoroSusuError::InvalidTier);
         }
 
-        let lending_pool: Address = env.storage().instance().get(&DataKey::LendingPool)
-            .unwrap_or_else(|| panic!("Lending pool not configured"));
+        // An approved change is locked in until the round it waits for is finalized
+        let request_key = DataKey::TierChangeRequest(circle_id, user);
+        if let Some(existing) = env.storage().instance().get::<DataKey, TierChangeRequest>(&request_key) {
+            if existing.approved {
+                return Err(SoroSusuError::ChangeAlreadyPending);
+            }
+        }
 
-        let lending_client = LendingPoolClient::new(&env, &lending_pool);
-        lending_client.supply(
-            &circle.token,
-            &env.current_contract_address(),
-            &amount,
-        );
+        env.storage().instance().set(&request_key, &TierChangeRequest { new_tier, approved: false });
 
-        circle.yield_deposited += amount;
-        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+        Ok(())
     }
 
-    fn prepare_payout_liquidity(env: Env, caller: Address, circle_id: u64) {
-        caller.require_auth();
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not initialized");
-        if caller != circle.creator && caller != stored_admin {
-            panic!("Unauthorized");
-        }
+    fn approve_tier_change(env: Env, creator: Address, circle_id: u64, member: Address) -> Result<(), SoroSusuError> {
+        creator.require_auth();
+        require_protocol_active(&env)?;
 
-        if circle.yield_deposited == 0 {
-            return;
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if creator != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
         }
 
-        let lending_pool: Address = env.storage().instance().get(&DataKey::LendingPool)
-            .unwrap_or_else(|| panic!("Lending pool not configured"));
-        let lending_client = LendingPoolClient::new(&env, &lending_pool);
-        lending_client.withdraw(
-            &circle.token,
-            &env.current_contract_address(),
-            &circle.yield_deposited,
-        );
+        let request_key = DataKey::TierChangeRequest(circle_id, member);
+        let mut request: TierChangeRequest = env.storage().instance().get(&request_key).ok_or(SoroSusuError::NoActiveProposal)?;
+        request.approved = true;
+        env.storage().instance().set(&request_key, &request);
 
-        circle.yield_deposited = 0;
-        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+        Ok(())
     }
 
-    fn trigger_insurance_coverage(env: Env, caller: Address, circle_id: u64, member: Address) {
-        caller.require_auth();
+    fn purge_exited_member(env: Env, member: Address) -> Result<(), SoroSusuError> {
+        require_protocol_active(&env)?;
 
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        let ejection_key = DataKey::EjectionTime(member.clone());
+        let ejected_at: u64 = env.storage().instance().get(&ejection_key).ok_or(SoroSusuError::PurgeTooEarly)?;
+        let purge_after_secs: u64 = env.storage().instance().get(&DataKey::PurgeAfterSecs).unwrap_or(DEFAULT_PURGE_AFTER_SECS);
 
-        // Only creator can trigger insurance
-        if caller != circle.creator {
-            panic!("Unauthorized: Only creator can trigger insurance");
+        if env.ledger().timestamp() < ejected_at + purge_after_secs {
+            return Err(SoroSusuError::PurgeTooEarly);
         }
 
-        // Check if insurance was already used this cycle
-        if circle.is_insurance_used {
-            panic!("Insurance already used this cycle");
+        if let Some(circle_id) = env.storage().instance().get::<DataKey, u64>(&DataKey::MemberCircle(member.clone())) {
+            // An ejected member keeps their seat until it is swept, and the sweep still reads their record
+            if let Some(circle) = env.storage().instance().get::<DataKey, CircleInfo>(&DataKey::Circle(circle_id)) {
+                if circle.member_addresses.contains(&member) {
+                    return Err(SoroSusuError::PurgeTooEarly);
+                }
+            }
+            env.storage().instance().remove(&DataKey::TierChangeRequest(circle_id, member.clone()));
         }
+        env.storage().instance().remove(&DataKey::Member(member.clone()));
+        env.storage().instance().remove(&DataKey::MemberCircle(member.clone()));
+        env.storage().instance().remove(&DataKey::MemberToken(member.clone()));
+        env.storage().instance().remove(&DataKey::MemberJoinTime(member.clone()));
+        env.storage().instance().remove(&DataKey::ContributionHistory(member));
+        env.storage().instance().remove(&ejection_key);
+
+        Ok(())
+    }
 
-        // Check if there is enough balance
-        if circle.insurance_balance < circle.contribution_amount {
-            panic!("Insufficient insurance balance");
-        }
+    fn get_member_circle(env: Env, member: Address) -> Result<u64, SoroSusuError> {
+        env.storage().instance()
+            .get(&DataKey::MemberCircle(member))
+            .ok_or(SoroSusuError::NotAMember)
+    }
 
-        let member_key = DataKey::Member(member.clone());
-        let member_info: Member = env.storage().instance().get(&member_key).unwrap();
+    fn get_member_join_time(env: Env, member: Address) -> Result<u64, SoroSusuError> {
+        env.storage().instance()
+            .get(&DataKey::MemberJoinTime(member))
+            .ok_or(SoroSusuError::NotAMember)
+    }
 
-        if member_info.status != MemberStatus::Active {
-            panic!("Member is not active");
-        }
+    fn get_referrer(env: Env, member: Address) -> Result<Option<Address>, SoroSusuError> {
+        let member: Member = env.storage().instance().get(&DataKey::Member(member)).ok_or(SoroSusuError::NotAMember)?;
+        Ok(member.referrer)
+    }
 
-        // Mark member as contributed in the bitmap
-        if (circle.contribution_bitmap & (1 << member_info.index)) != 0 {
-            panic!("Member already contributed");
-        }
+    fn get_member_badges(env: Env, member: Address) -> Vec<BadgeType> {
+        env.storage().instance().get(&MetadataKey::MemberBadges(member)).unwrap_or_else(|| Vec::new(&env))
+    }
 
-        circle.contribution_bitmap |= 1 << member_info.index;
-        circle.insurance_balance -= circle.contribution_amount;
-        circle.is_insurance_used = true;
+    fn get_referral_count(env: Env, referrer: Address) -> Result<u32, SoroSusuError> {
+        Ok(env.storage().instance().get(&MetadataKey::ReferralCount(referrer)).unwrap_or(0))
+    }
 
-        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    fn get_member_status_counts(env: Env, circle_id: u64) -> Result<(u32, u32, u32, u32), SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(member_status_counts(&env, &circle))
     }
 
-    fn propose_penalty_change(env: Env, user: Address, circle_id: u64, new_bps: u32) {
-        user.require_auth();
-        
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        
-        // Check if user is a member
-        let member_key = DataKey::Member(user.clone());
-        let member: Member = env.storage().instance().get(&member_key).expect("User is not a member");
+    fn is_circle_full(env: Env, circle_id: u64) -> Result<bool, SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(circle.member_count >= circle.max_members)
+    }
 
-        if !member.is_active {
-            panic!("Member is ejected");
-        }
-        if member.status != MemberStatus::Active {
-            panic!("Member is not active");
+    fn get_member_count(env: Env, circle_id: u64) -> Result<u32, SoroSusuError> {
+        if let Some(count) = env.storage().instance().get(&DataKey::CircleMemberCount(circle_id)) {
+            return Ok(count);
         }
+        // Circles created before the count was kept separately
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        Ok(circle.member_count)
+    }
 
-        if new_bps > 10000 {
-            panic!("Penalty cannot exceed 100%");
-        }
+    fn get_join_order(env: Env, circle_id: u64) -> Result<Vec<Address>, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
+        Ok(env.storage().instance().get(&MetadataKey::JoinOrder(circle_id)).unwrap_or_else(|| Vec::new(&env)))
+    }
 
-        // Set proposal
-        circle.proposed_late_fee_bps = new_bps;
-        circle.proposal_votes_bitmap = 0;
-        
-        // Auto-vote for proposer
-        circle.proposal_votes_bitmap |= 1 << member.index;
+    fn refresh_ttl(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_circle_exists(&env, circle_id)?;
 
-        // Check for immediate majority (e.g. 1 member circle)
-        if circle.proposal_votes_bitmap.count_ones() > (circle.member_count as u32 / 2) {
-            circle.late_fee_bps = circle.proposed_late_fee_bps;
-            circle.proposed_late_fee_bps = 0;
-            circle.proposal_votes_bitmap = 0;
+        let caller_circle: Option<u64> = env.storage().instance().get(&DataKey::MemberCircle(caller.clone()));
+        if caller_circle != Some(circle_id) {
+            return Err(SoroSusuError::NotAMember);
+        }
+        let member: Member = env.storage().instance().get(&DataKey::Member(caller)).ok_or(SoroSusuError::NotAMember)?;
+        if member.status != MemberStatus::Active {
+            return Err(SoroSusuError::MemberNotActive);
         }
 
-        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
-    }
+        // Circle, member, contribution and deposit entries all live in instance storage,
+        // so extending the instance keeps every one of them alive
+        env.storage().instance().extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
 
-    fn propose_duration_change(env: Env, user: Address, circle_id: u64, new_duration: u64) {
-        user.require_auth();
+        Ok(())
+    }
 
-        if new_duration == 0 {
-            panic!("Duration must be greater than zero");
+    fn set_admin_list(env: Env, admin: Address, admins: Vec<Address>, threshold: u32) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(SoroSusuError::Unauthorized);
         }
 
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        let protocol_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not initialized");
-
-        if user != circle.creator && user != protocol_admin {
-            panic!("Unauthorized: Only admin can propose duration changes");
+        if threshold == 0 || threshold > admins.len() {
+            return Err(SoroSusuError::InvalidThreshold);
         }
 
-        let current_time = env.ledger().timestamp();
-        circle.pending_cycle_duration = new_duration;
-        circle.duration_change_effective_at = current_time + DURATION_CHANGE_NOTICE_SECS;
+        env.storage().instance().set(&DataKey::AdminList, &admins);
+        env.storage().instance().set(&DataKey::ApprovalThreshold, &threshold);
 
-        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+        Ok(())
     }
 
-    fn vote_penalty_change(env: Env, user: Address, circle_id: u64) {
+    fn claim_pot(env: Env, user: Address, circle_id: u64) -> Result<(), SoroSusuError> {
         user.require_auth();
+        require_protocol_active(&env)?;
 
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        
-        // Check if user is a member
-        let member_key = DataKey::Member(user.clone());
-        let member: Member = env.storage().instance().get(&member_key).expect("User is not a member");
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
 
-        if member.status != MemberStatus::Active {
-            panic!("Member is not active");
+        // All three must hold together: finalized round, recorded schedule, schedule reached
+        if !circle.is_round_finalized {
+            return Err(SoroSusuError::RoundNotFinalized);
         }
 
-        if circle.proposed_late_fee_bps == 0 {
-            panic!("No active proposal");
+        let scheduled_payout_time: u64 = env.storage().instance()
+            .get(&DataKey::ScheduledPayoutTime(circle_id))
+            .ok_or(SoroSusuError::PayoutNotScheduled)?;
+
+        if env.ledger().timestamp() < scheduled_payout_time {
+            return Err(SoroSusuError::PayoutTooEarly);
         }
 
-        circle.proposal_votes_bitmap |= 1 << member.index;
+        if user != circle.current_pot_recipient {
+            return Err(SoroSusuError::Unauthorized);
+        }
 
-        if circle.proposal_votes_bitmap.count_ones() > (circle.member_count as u32 / 2) {
-            circle.late_fee_bps = circle.proposed_late_fee_bps;
-            circle.proposed_late_fee_bps = 0;
-            circle.proposal_votes_bitmap = 0;
+        // Contributions made in other accepted tokens are paid out in those tokens; the rest in the payout token
+        let mut payout_amount: u64 = env.storage().instance().get(&DataKey::RoundPot(circle_id)).unwrap_or(0);
+        let token_pot: Map<Address, u64> = env.storage().instance().get(&DataKey::RoundTokenPot(circle_id)).unwrap_or_else(|| Map::new(&env));
+        for (deposit_token, amount) in token_pot.iter() {
+            token::Client::new(&env, &deposit_token).transfer(&env.current_contract_address(), &user, &(amount as i128));
+            payout_amount = payout_amount.saturating_sub(amount);
         }
 
+        if payout_amount > 0 {
+            let token_client = token::Client::new(&env, &circle.token);
+            token_client.transfer(&env.current_contract_address(), &user, &(payout_amount as i128));
+        }
+
+        env.storage().instance().remove(&DataKey::RoundPot(circle_id));
+        env.storage().instance().remove(&DataKey::RoundTokenPot(circle_id));
+        env.storage().instance().remove(&DataKey::ScheduledPayoutTime(circle_id));
+        circle.is_round_finalized = false;
         env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
-    }
 
-    fn propose_address_change(env: Env, user: Address, circle_id: u64, old_address: Address, new_address: Address) {
-        user.require_auth();
+        Ok(())
+    }
 
-        if old_address == new_address {
-            panic!("Old and new addresses must differ");
+    fn simulate_payout(env: Env, circle_id: u64) -> Result<(u64, u64), SoroSusuError> {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if !circle.is_round_finalized {
+            return Ok((0, 0));
         }
 
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        // No protocol fee is taken from payouts yet, so the recipient gets the whole pot
+        let round_pot: u64 = env.storage().instance().get(&DataKey::RoundPot(circle_id)).unwrap_or(0);
+        Ok((round_pot, round_pot))
+    }
+
+    fn cancel_round(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
 
-        let proposer_key = DataKey::Member(user.clone());
-        let proposer: Member = env.storage().instance().get(&proposer_key).expect("User is not a member");
-        if proposer.status != MemberStatus::Active {
-            panic!("Member is not active");
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        let protocol_admin: Address = env.storage().instance().get(&DataKey::Admin).ok_or(SoroSusuError::NotInitialized)?;
+        if caller != circle.creator && caller != protocol_admin {
+            return Err(SoroSusuError::Unauthorized);
         }
-
-        let old_member_key = DataKey::Member(old_address.clone());
-        let old_member: Member = env.storage().instance().get(&old_member_key).expect("Old address is not a member");
-        if old_member.status != MemberStatus::Active {
-            panic!("Old address member is not active");
+        if !circle.is_active {
+            return Err(SoroSusuError::CircleClosed);
         }
 
-        let new_member_key = DataKey::Member(new_address.clone());
-        if env.storage().instance().has(&new_member_key) {
-            panic!("New address is already a member");
+        // A finalized pot already belongs to its recipient
+        if circle.is_round_finalized {
+            return Err(SoroSusuError::RoundAlreadyFinalized);
         }
 
-        circle.recovery_old_address = Some(old_address);
-        circle.recovery_new_address = Some(new_address);
-        circle.recovery_votes_bitmap = 0;
-        circle.recovery_votes_bitmap |= 1 << proposer.index;
+        let totals_key = DataKey::RoundTokenContributions(circle_id);
+        let mut totals: Map<Address, u64> = env.storage().instance().get(&totals_key).unwrap_or_else(|| Map::new(&env));
+        let mut refunded = 0u64;
+        for index in 0..circle.member_count {
+            if (circle.contribution_bitmap >> index) & 1 == 0 {
+                continue;
+            }
+            let member_address = get_member_address_by_index(&circle, index).ok_or(SoroSusuError::InvalidRecipientIndex)?;
+            let member_key = DataKey::Member(member_address.clone());
+            let mut member: Member = env.storage().instance().get(&member_key).ok_or(SoroSusuError::NotAMember)?;
+
+            let contribution_key = DataKey::CurrentRoundContribution(circle_id, index);
+            let amount: u64 = env.storage().instance()
+                .get(&contribution_key)
+                .unwrap_or(circle.contribution_amount * member.tier_multiplier as u64);
+            env.storage().instance().remove(&contribution_key);
+
+            let deposit_key = DataKey::Deposit(circle_id, member_address.clone());
+            match env.storage().instance().get::<DataKey, Address>(&deposit_key) {
+                Some(deposit_token) => {
+                    token::Client::new(&env, &deposit_token).transfer(&env.current_contract_address(), &member_address, &(amount as i128));
+                    if let Some(collected) = totals.get(deposit_token.clone()) {
+                        totals.set(deposit_token, collected.saturating_sub(amount));
+                    }
+
+                    member.contribution_count = member.contribution_count.saturating_sub(1);
+                    member.total_contributed = member.total_contributed.saturating_sub(amount);
+                    env.storage().instance().set(&member_key, &member);
+                    env.storage().instance().remove(&deposit_key);
+                    refunded += amount;
+                }
+                None => {
+                    // Covered by insurance: the money never left the pool's tokens, so it goes back there
+                    let balance = insurance_fund(&env, circle_id);
+                    env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &(balance + amount));
+                    circle.is_insurance_used = false;
+                }
+            }
+        }
+        env.storage().instance().remove(&totals_key);
 
-        apply_recovery_if_consensus(&env, &mut circle);
+        circle.contribution_bitmap = 0;
+        circle.late_contribution_bitmap = 0;
+        circle.is_round_finalized = false;
         env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+
+        env.events().publish((Symbol::new(&env, "ROUND_CANCELLED"), circle_id), refunded);
+
+        Ok(())
     }
 
-    fn vote_for_recovery(env: Env, user: Address, circle_id: u64) {
-        user.require_auth();
+    fn close_circle(env: Env, caller: Address, circle_id: u64) -> Result<(), SoroSusuError> {
+        caller.require_auth();
+        require_protocol_active(&env)?;
 
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        if circle.recovery_old_address.is_none() || circle.recovery_new_address.is_none() {
-            panic!("No active recovery proposal");
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).ok_or(SoroSusuError::CircleNotFound)?;
+        if caller != circle.creator {
+            return Err(SoroSusuError::Unauthorized);
         }
-
-        let member_key = DataKey::Member(user.clone());
-        let member: Member = env.storage().instance().get(&member_key).expect("User is not a member");
-        if member.status != MemberStatus::Active {
-            panic!("Member is not active");
+        if !circle.is_active {
+            return Err(SoroSusuError::CircleClosed);
         }
 
-        circle.recovery_votes_bitmap |= 1 << member.index;
-        apply_recovery_if_consensus(&env, &mut circle);
+        // Every seat has received a pot and the last one has been claimed
+        let all_paid = circle.member_count > 0
+            && circle.payout_bitmap == u64::MAX >> (64 - circle.member_count);
+        if !all_paid || circle.is_round_finalized {
+            return Err(SoroSusuError::CircleNotComplete);
+        }
 
+        award_completion_badges(&env, &circle);
+        refund_insurance_on_close(&env, &circle)?;
+        circle.is_active = false;
         env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
-    }
-
-    fn eject_member(env: Env, caller: Address, circle_id: u64, member: Address) {
-        caller.require_auth();
-        
-        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        
-        // Only creator can eject
-        if caller != circle.creator {
-            panic!("Unauthorized: Only creator can eject members");
-        }
 
-        let member_key = DataKey::Member(member.clone());
-        let mut member_info: Member = env.storage().instance().get(&member_key).expect("Member not found");
+        Ok(())
+    }
 
-        if member_info.status != MemberStatus::Active {
-            panic!("Member already ejected");
-        }
+    fn propose_eject_member(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<u64, SoroSusuError> {
+        create_operation(&env, caller, circle_id, 1, Some(member))
+    }
 
-        // Mark as ejected
-        member_info.status = MemberStatus::Ejected;
-        env.storage().instance().set(&member_key, &member_info);
+    fn propose_finalize_round(env: Env, caller: Address, circle_id: u64) -> Result<u64, SoroSusuError> {
+        create_operation(&env, caller, circle_id, 2, None)
+    }
 
-        // Burn NFT
-        let token_id = (circle_id as u128) << 64 | (member_info.index as u128);
-        let client = SusuNftClient::new(&env, &circle.nft_contract);
-        client.burn(&member, &token_id);
+    fn propose_trigger_insurance(env: Env, caller: Address, circle_id: u64, member: Address) -> Result<u64, SoroSusuError> {
+        create_operation(&env, caller, circle_id, 3, Some(member))
     }
 
-    fn request_exit(env: Env, user: Address, circle_id: u64) {
-        user.require_auth();
+    fn admin_zero_yield_deposited(env: Env, caller: Address, circle_id: u64) -> Result<u64, SoroSusuError> {
+        create_operation(&env, caller, circle_id, 4, None)
+    }
 
-        // Get the circle and member information
-        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id))
-            .unwrap_or_else(|| panic!("Circle not found"));
+    fn approve_operation(env: Env, admin: Address, operation_id: u64, nonce: u64) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        require_multisig_admin(&env, &admin)?;
+        consume_admin_nonce(&env, &admin, nonce)?;
 
-        let member_key = DataKey::Member(user.clone());
-        let mut member: Member = env.storage().instance().get(&member_key)
-            .unwrap_or_else(|| panic!("User is not a member of this circle"));
+        let mut operation: AdminOperation = env.storage().instance()
+            .get(&DataKey::Operation(operation_id))
+            .ok_or(SoroSusuError::OperationNotFound)?;
 
-        // Check if member is active and can request exit
-        if member.status != MemberStatus::Active {
-            panic!("Member cannot request exit in current state");
+        if operation.is_executed {
+            return Err(SoroSusuError::OperationAlreadyExecuted);
         }
 
-        // Change member status to AwaitingReplacement
-        member.status = MemberStatus::AwaitingReplacement;
-        env.storage().instance().set(&member_key, &member);
+        approve_live_operation(&env, admin, &mut operation)
+    }
 
-        // Store the pending exit request
-        let pending_exit_key = DataKey::PendingExit(circle_id, user.clone());
-        env.storage().instance().set(&pending_exit_key, &true);
+    fn batch_approve_operations(env: Env, admin: Address, operation_ids: Vec<u64>, nonce: u64) -> Result<(), SoroSusuError> {
+        admin.require_auth();
+        require_multisig_admin(&env, &admin)?;
+        consume_admin_nonce(&env, &admin, nonce)?;
+
+        for operation_id in operation_ids.iter() {
+            // A stale or already-signed id should not roll back the approvals around it
+            let operation: Option<AdminOperation> = env.storage().instance().get(&DataKey::Operation(operation_id));
+            let mut operation = match operation {
+                Some(operation) if !operation.is_executed && !operation.approvals.contains(&admin) => operation,
+                _ => {
+                    env.events().publish((Symbol::new(&env, "SKIPPED_OPERATION"),), operation_id);
+                    continue;
+                }
+            };
+            approve_live_operation(&env, admin.clone(), &mut operation)?;
+        }
 
-        // Note: We keep the member's position in the queue locked until fill_vacancy is called
+        Ok(())
     }
 
-    fn fill_vacancy(env: Env, new_member: Address, circle_id: u64, exiting_member_address: Address) {
-        new_member.require_auth();
+    fn get_admin_nonce(env: Env, admin: Address) -> u64 {
+        env.storage().instance().get(&DataKey::OperationNonce(admin)).unwrap_or(0)
+    }
 
-        // Get the circle information
-        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id))
-            .unwrap_or_else(|| panic!("Circle not found"));
+    fn cancel_expired_operation(env: Env, operation_id: u64) -> Result<(), SoroSusuError> {
+        let operation: AdminOperation = env.storage().instance()
+            .get(&DataKey::Operation(operation_id))
+            .ok_or(SoroSusuError::OperationNotFound)?;
 
-        // Verify there's a pending exit for the specified member
-        let pending_exit_key = DataKey::PendingExit(circle_id, exiting_member_address.clone());
-        if !env.storage().instance().has(&pending_exit_key) {
-            panic!("No pending exit found for specified member");
+        if operation.is_executed {
+            return Err(SoroSusuError::OperationAlreadyExecuted);
         }
 
-        // Get the exiting member's information
-        let exiting_member_key = DataKey::Member(exiting_member_address.clone());
-        let exiting_member: Member = env.storage().instance().get(&exiting_member_key)
-            .unwrap_or_else(|| panic!("Exiting member not found"));
-
-        if exiting_member.status != MemberStatus::AwaitingReplacement {
-            panic!("Exiting member is not in AwaitingReplacement state");
+        if env.ledger().timestamp() <= operation.expiry_timestamp {
+            return Err(SoroSusuError::OperationNotExpired);
         }
 
-        // Check if new member is already in any circle
-        let new_member_key = DataKey::Member(new_member.clone());
-        if env.storage().instance().has(&new_member_key) {
-            panic!("New member is already part of a circle");
-        }
+        env.storage().instance().remove(&DataKey::Operation(operation_id));
 
-        // Calculate pot amount based on sum of current round contributions
-        let mut pot_amount = 0u64;
-        
-        // Sum up all individual contributions for the current round
-        for i in 0..circle.member_count {
-            let contribution_key = DataKey::CurrentRoundContribution(circle_id, i as u32);
-            if let Some(contribution) = env.storage().instance().get::<DataKey, u64>(&contribution_key) {
-                pot_amount += contribution;
+        let ids_key = DataKey::CircleOperations(operation.circle_id);
+        if let Some(mut ids) = env.storage().instance().get::<DataKey, Vec<u64>>(&ids_key) {
+            if let Some(position) = ids.first_index_of(operation_id) {
+                ids.remove(position);
+                env.storage().instance().set(&ids_key, &ids);
             }
         }
-        
-        // Fallback to calculation if no individual contributions tracked (for backwards compatibility)
-        if pot_amount == 0 {
-            pot_amount = circle.contribution_amount * circle.member_count as u64;
-        }
 
-        // Calculate refund amount on the fly (principal only).
-        let refund_amount = exiting_member.contribution_count as u64 * circle.contribution_amount;
+        let pending_key = DataKey::PendingOperationCount(operation.circle_id);
+        let pending: u32 = env.storage().instance().get(&pending_key).unwrap_or(0);
+        env.storage().instance().set(&pending_key, &pending.saturating_sub(1));
 
-        if refund_amount > 0 {
-            // Transfer refund to exiting member
-            let token_client = token::Client::new(&env, &circle.token);
-            let refund_amount_i128 = refund_amount as i128;
-            token_client.transfer(
-                &env.current_contract_address(),
-                &exiting_member_address,
-                &refund_amount_i128
-            );
-        }
+        env.events().publish(
+            (Symbol::new(&env, "OPERATION_EXPIRED"), operation.circle_id),
+            operation_id,
+        );
 
-        // Create new member with the same index as the exiting member
-        let replacement_member = Member {
-            address: new_member.clone(),
-            index: exiting_member.index, // Inherit the position in queue
-            contribution_count: 0,
-            last_contribution_time: 0,
-            is_active: true,
-            tier_multiplier: 1,
-            status: MemberStatus::Active,
-            total_contributed: 0,
-            referrer: None,
-        };
+        Ok(())
+    }
 
-        // Store the new member
-        env.storage().instance().set(&new_member_key, &replacement_member);
+    fn get_operation(env: Env, operation_id: u64) -> Result<AdminOperation, SoroSusuError> {
+        env.storage().instance()
+            .get(&DataKey::Operation(operation_id))
+            .ok_or(SoroSusuError::OperationNotFound)
+    }
 
-        // Update exiting member status to Ejected (effectively removed)
-        let mut updated_exiting_member = exiting_member.clone();
-        updated_exiting_member.status = MemberStatus::Ejected;
-        env.storage().instance().set(&exiting_member_key, &updated_exiting_member);
+    fn get_pending_operations(env: Env, circle_id: u64) -> Result<Vec<AdminOperation>, SoroSusuError> {
+        require_circle_exists(&env, circle_id)?;
 
-        // Remove the pending exit record
-        env.storage().instance().remove(&pending_exit_key);
+        let ids: Vec<u64> = env.storage().instance()
+            .get(&DataKey::CircleOperations(circle_id))
+            .unwrap_or_else(|| Vec::new(&env));
 
-        // Burn the exiting member's NFT
-        let token_id = (circle_id as u128) << 64 | (exiting_member.index as u128);
-        let nft_client = SusuNftClient::new(&env, &circle.nft_contract);
-        nft_client.burn(&exiting_member_address, &token_id);
+        let mut pending = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(operation) = env.storage().instance().get::<DataKey, AdminOperation>(&DataKey::Operation(id)) {
+                if !operation.is_executed {
+                    pending.push_back(operation);
+                }
+            }
+        }
+        Ok(pending)
+    }
 
-        // Mint new NFT for the replacement member
-        nft_client.mint(&new_member, &token_id);
+    fn get_operation_approvers(env: Env, operation_id: u64) -> Result<Vec<Address>, SoroSusuError> {
+        let operation = Self::get_operation(env, operation_id)?;
+        Ok(operation.approvals)
+    }
+
+    fn get_remaining_approvals_needed(env: Env, operation_id: u64) -> Result<u32, SoroSusuError> {
+        let operation = Self::get_operation(env.clone(), operation_id)?;
+        if operation.is_executed {
+            return Ok(0);
+        }
+        let threshold = approval_threshold(&env, &operation);
+        Ok(threshold.saturating_sub(operation.approvals.len()))
     }
 }
 
@@ -1490,13 +4990,12 @@ mod fuzz_tests {
         // User 1 deposits. 1000 + 100 fee. Insurance balance becomes 100.
         SoroSusuTrait::deposit(env.clone(), user1.clone(), circle_id);
         
-        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
-        assert_eq!(circle.insurance_balance, 100);
+        let balance: u64 = env.storage().instance().get(&DataKey::InsuranceFund(circle_id)).unwrap();
+        assert_eq!(balance, 100);
 
         // User 1 deposits 9 more times to build up insurance (simulating multiple cycles or members)
         // In this simplified test, we just force update the balance to test triggering
-        circle.insurance_balance = 1000; 
-        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+        env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &1000u64);
 
         // User 2 defaults. Creator triggers insurance.
         SoroSusuTrait::trigger_insurance_coverage(env.clone(), creator.clone(), circle_id, user2.clone());
@@ -1506,7 +5005,7 @@ mod fuzz_tests {
         let member2: Member = env.storage().instance().get(&member2_key).unwrap();
 
         assert!(circle_after.is_insurance_used);
-        assert_eq!(circle_after.insurance_balance, 0);
+        assert_eq!(SoroSusuTrait::get_insurance_fund(env.clone(), circle_id), Ok(0));
         assert!(circle_after.contribution_bitmap & (1 << member2.index) != 0);
     }
 
@@ -1590,7 +5089,6 @@ mod fuzz_tests {
         // Verify member is active
         let member_key = DataKey::Member(user.clone());
         let member: Member = env.storage().instance().get(&member_key).unwrap();
-        assert!(member.is_active);
         assert_eq!(member.status, MemberStatus::Active);
 
         // Check that round is finalized and scheduled payout time is set
@@ -1601,7 +5099,6 @@ mod fuzz_tests {
         SoroSusuTrait::eject_member(env.clone(), creator.clone(), circle_id, user.clone());
 
         let member_after: Member = env.storage().instance().get(&member_key).unwrap();
-        assert!(!member_after.is_active);
         assert_eq!(member_after.status, MemberStatus::Ejected);
 
         // Inactive member cannot deposit
@@ -0,0 +1,400 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as TestAddress,
+    vec, Address, Bytes, Env, Symbol,
+};
+use sorosusu_contracts::{CircleCategory, CircleInfo, DataKey, ProtocolConfig, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+#[test]
+fn circles_by_creator_keeps_insertion_order() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let other_creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let first = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other = client.create_circle(&other_creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let second = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let third = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    assert_eq!(client.get_circles_by_creator(&creator), vec![&env, first, second, third]);
+    assert_eq!(client.get_circles_by_creator(&other_creator), vec![&env, other]);
+    assert_eq!(client.get_circles_by_creator(&admin).len(), 0);
+}
+
+#[test]
+fn circle_token_view_matches_stored_circle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let other_token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let first = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let second = client.create_circle(&creator, &1_000, &4, &other_token, &604_800, &0, &0, &0, &nft_contract);
+    let cloned = client.clone_circle(&creator, &second);
+
+    for circle_id in [first, second, cloned] {
+        let stored: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+        assert_eq!(client.get_circle_token(&circle_id), stored.token);
+    }
+    assert_eq!(client.get_circle_token(&first), token);
+    assert_eq!(client.get_circle_token(&cloned), other_token);
+    assert_eq!(client.try_get_circle_token(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn circle_description_round_trips() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    assert_eq!(client.get_circle_description(&circle_id), (Symbol::new(&env, ""), Bytes::new(&env)));
+
+    let name = Symbol::new(&env, "market_women");
+    let description = Bytes::from_slice(&env, b"Weekly savings for the Saturday market stalls");
+    client.set_circle_description(&creator, &circle_id, &name, &description);
+    assert_eq!(client.get_circle_description(&circle_id), (name.clone(), description.clone()));
+
+    assert_eq!(
+        client.try_set_circle_description(&admin, &circle_id, &name, &description),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_circle_description(&creator, &circle_id, &Symbol::new(&env, ""), &description),
+        Err(Ok(SoroSusuError::InvalidOperation))
+    );
+    assert_eq!(client.try_get_circle_description(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn circles_are_listed_by_category() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let savings = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let emergency = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_circle_category(&creator, &emergency, &CircleCategory::Emergency);
+
+    assert_eq!(client.get_circle_category(&savings), CircleCategory::Savings);
+    assert_eq!(client.get_circles_by_category(&CircleCategory::Savings, &0, &10), vec![&env, savings]);
+    assert_eq!(client.get_circles_by_category(&CircleCategory::Emergency, &0, &10), vec![&env, emergency]);
+    assert_eq!(client.get_circles_by_category(&CircleCategory::Business, &0, &10).len(), 0);
+
+    // Clones keep the source's category; paging walks the category's own list
+    let cloned = client.clone_circle(&creator, &emergency);
+    assert_eq!(client.get_circles_by_category(&CircleCategory::Emergency, &1, &10), vec![&env, cloned]);
+
+    assert_eq!(
+        client.try_set_circle_category(&admin, &savings, &CircleCategory::Business),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+    assert_eq!(client.try_get_circle_category(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+fn create_with_duration(cycle_duration: u64) -> Result<u64, SoroSusuError> {
+    create_with(1_000, cycle_duration, 0)
+}
+
+fn create_with(amount: u64, cycle_duration: u64, insurance_fee_bps: u32) -> Result<u64, SoroSusuError> {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    match client.try_create_circle(&creator, &amount, &4, &token, &cycle_duration, &0, &insurance_fee_bps, &0, &nft_contract) {
+        Ok(circle_id) => Ok(circle_id.unwrap()),
+        Err(error) => Err(error.unwrap()),
+    }
+}
+
+#[test]
+fn cycle_duration_at_minimum_is_accepted() {
+    assert_eq!(create_with_duration(3_600), Ok(1));
+}
+
+#[test]
+fn cycle_duration_below_minimum_is_rejected() {
+    assert_eq!(create_with_duration(3_599), Err(SoroSusuError::InvalidCycleDuration));
+}
+
+#[test]
+fn cycle_duration_at_maximum_is_accepted() {
+    assert_eq!(create_with_duration(365 * 24 * 3600), Ok(1));
+}
+
+#[test]
+fn cycle_duration_above_maximum_is_rejected() {
+    assert_eq!(create_with_duration(365 * 24 * 3600 + 1), Err(SoroSusuError::InvalidCycleDuration));
+}
+
+#[test]
+fn cycle_duration_minimum_follows_protocol_config() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let config = ProtocolConfig { min_cycle_duration_secs: 86_400, ..client.get_protocol_config() };
+    client.set_protocol_config(&admin, &config);
+
+    assert_eq!(
+        client.try_create_circle(&creator, &1_000, &4, &token, &3_600, &0, &0, &0, &nft_contract),
+        Err(Ok(SoroSusuError::InvalidCycleDuration))
+    );
+    client.create_circle(&creator, &1_000, &4, &token, &86_400, &0, &0, &0, &nft_contract);
+}
+
+#[test]
+fn insurance_fee_at_cap_is_accepted() {
+    assert_eq!(create_with(1_000, 604_800, 1_000), Ok(1));
+}
+
+#[test]
+fn insurance_fee_above_cap_is_rejected() {
+    assert_eq!(create_with(1_000, 604_800, 1_001), Err(SoroSusuError::InvalidBasisPoints));
+}
+
+#[test]
+fn uninsured_circle_is_accepted() {
+    // A zero fee is a deliberate choice: the circle simply has no insurance pool
+    assert_eq!(create_with(1_000, 604_800, 0), Ok(1));
+}
+
+#[test]
+fn zero_contribution_amount_is_rejected() {
+    assert_eq!(create_with(0, 604_800, 0), Err(SoroSusuError::InvalidAmount));
+    assert_eq!(create_with(1, 604_800, 0), Ok(1));
+}
+
+#[test]
+fn cloned_circle_copies_settings_but_not_state() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let cloner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let source_id = client.create_circle(&creator, &2_500, &6, &token, &86_400, &3_600, &150, &4_000, &nft_contract);
+    client.admin_set_late_fee_bps(&creator, &source_id, &300);
+    client.set_priority_ordering(&creator, &source_id, &true);
+    client.join_circle(&member, &source_id, &1);
+
+    let clone_id = client.clone_circle(&cloner, &source_id);
+    assert_ne!(clone_id, source_id);
+    assert_eq!(client.get_circles_by_creator(&cloner), vec![&env, clone_id]);
+
+    let (source, clone) = env.as_contract(&contract_id, || {
+        let source: CircleInfo = env.storage().instance().get(&DataKey::Circle(source_id)).unwrap();
+        let clone: CircleInfo = env.storage().instance().get(&DataKey::Circle(clone_id)).unwrap();
+        (source, clone)
+    });
+    assert_eq!(clone.creator, cloner);
+    assert_eq!(clone.contribution_amount, source.contribution_amount);
+    assert_eq!(clone.max_members, source.max_members);
+    assert_eq!(clone.token, source.token);
+    assert_eq!(clone.cycle_duration, source.cycle_duration);
+    assert_eq!(clone.grace_period_secs, source.grace_period_secs);
+    assert_eq!(clone.insurance_fee_bps, source.insurance_fee_bps);
+    assert_eq!(clone.max_single_member_share_bps, source.max_single_member_share_bps);
+    assert_eq!(clone.late_fee_bps, 300);
+    assert!(clone.priority_ordering);
+    assert_eq!(clone.nft_contract, source.nft_contract);
+    assert_eq!(clone.member_count, 0);
+
+    // Changing the clone leaves the source untouched
+    client.admin_set_late_fee_bps(&cloner, &clone_id, &50);
+    assert_eq!(client.try_admin_set_late_fee_bps(&cloner, &source_id, &50), Err(Ok(SoroSusuError::Unauthorized)));
+    env.as_contract(&contract_id, || {
+        let source: CircleInfo = env.storage().instance().get(&DataKey::Circle(source_id)).unwrap();
+        assert_eq!(source.late_fee_bps, 300);
+        assert_eq!(source.member_count, 1);
+    });
+
+    assert_eq!(client.try_clone_circle(&cloner, &99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn sixty_fifth_member_is_rejected() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &64, &token, &604_800, &0, &0, &0, &nft_contract);
+    for _ in 0..64 {
+        client.join_circle(&Address::generate(&env), &circle_id, &1);
+    }
+
+    assert_eq!(client.try_join_circle(&Address::generate(&env), &circle_id, &1), Err(Ok(SoroSusuError::CircleFull)));
+
+    // Even if max_members were pushed past 64, no index beyond the bitmap width is handed out
+    env.as_contract(&contract_id, || {
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.max_members = 65;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    });
+    assert_eq!(
+        client.try_join_circle(&Address::generate(&env), &circle_id, &1),
+        Err(Ok(SoroSusuError::MaxMembersExceeded))
+    );
+}
+
+#[test]
+fn circle_count_tracks_every_creation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    for expected_id in 1..=10u64 {
+        let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+        assert_eq!(circle_id, expected_id);
+    }
+
+    // Re-initializing must not reset the counter
+    client.init(&admin);
+
+    let count: u64 = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::CircleCount).unwrap());
+    assert_eq!(count, 10);
+}
+
+#[test]
+fn active_circles_are_listed_page_by_page() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    for _ in 0..10 {
+        client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    }
+    env.as_contract(&contract_id, || {
+        for circle_id in [2u64, 5, 9] {
+            let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+            circle.is_active = false;
+            env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+        }
+    });
+
+    assert_eq!(client.get_active_circles(&1, &10), vec![&env, 1, 3, 4, 6, 7, 8, 10]);
+    assert_eq!(client.get_active_circles(&1, &5), vec![&env, 1, 3, 4]);
+    assert_eq!(client.get_active_circles(&6, &5), vec![&env, 6, 7, 8, 10]);
+    assert_eq!(client.get_active_circles(&11, &5), vec![&env]);
+
+    // Ids past the last circle and oversized limits are simply cut off
+    assert_eq!(client.get_active_circles(&8, &u64::MAX), vec![&env, 8, 10]);
+}
+
+#[test]
+fn active_circles_scan_at_most_fifty_ids() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    client.init(&admin);
+    for _ in 0..55 {
+        client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    }
+
+    let page = client.get_active_circles(&1, &100);
+    assert_eq!(page.len(), 50);
+    assert_eq!(page.last(), Some(50));
+    assert_eq!(client.get_active_circles(&51, &100).len(), 5);
+}
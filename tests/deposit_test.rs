@@ -0,0 +1,576 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Events, Ledger},
+    token, vec, Address, Env, FromVal, IntoVal, Symbol,
+};
+use sorosusu_contracts::{BadgeType, CircleInfo, DataKey, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+#[contract]
+pub struct MockLendingPool;
+
+#[contractimpl]
+impl MockLendingPool {
+    pub fn supply(_env: Env, _token: Address, _from: Address, _amount: u64) {}
+    pub fn withdraw(_env: Env, _token: Address, _to: Address, _amount: u64) {}
+    pub fn get_lending_pool_version(_env: Env) -> u32 {
+        1
+    }
+}
+
+#[test]
+fn late_deposit_after_yield_recall_applies_penalty() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let early_payer = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockLendingPool);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+    token::StellarAssetClient::new(&env, &token).mint(&early_payer, &10_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+    client.join_circle(&early_payer, &circle_id, &1);
+    client.deposit(&early_payer, &circle_id);
+
+    client.deposit_to_yield_pool(&creator, &circle_id, &500);
+
+    // Jump past the deadline: the same deposit must recall yield and charge the late fee
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&member, &circle_id);
+
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.yield_deposited, 0);
+        assert_eq!(circle.deadline_timestamp, env.ledger().timestamp() + 604_800);
+
+        let reserve: u64 = env.storage().instance().get(&DataKey::GroupReserve).unwrap();
+        assert_eq!(reserve, 10);
+    });
+}
+
+fn setup_token_circle<'a>(env: &Env, grace_period_secs: u64, member_count: u32) -> (SoroSusuClient<'a>, Address, u64, std::vec::Vec<Address>) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &5, &token, &604_800, &grace_period_secs, &0, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..member_count {
+        let member = Address::generate(env);
+        token::StellarAssetClient::new(env, &token).mint(&member, &25_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    (client, contract_id, circle_id, members)
+}
+
+fn group_reserve(env: &Env, contract_id: &Address) -> u64 {
+    env.as_contract(contract_id, || env.storage().instance().get(&DataKey::GroupReserve).unwrap_or(0))
+}
+
+// Clear this round's contribution bits so the same member can deposit again
+fn start_next_round(env: &Env, contract_id: &Address, circle_id: u64) {
+    env.as_contract(contract_id, || {
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.contribution_bitmap = 0;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    });
+}
+
+#[test]
+fn deposit_into_another_circle_is_rejected() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 1);
+
+    // A seat with the same index in another circle must not be credited
+    let creator = Address::generate(&env);
+    let other_member = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let other_circle = client.create_circle(&creator, &1_000, &5, &Address::generate(&env), &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&other_member, &other_circle, &1);
+
+    assert_eq!(client.try_deposit(&members[0], &other_circle), Err(Ok(SoroSusuError::NotAMember)));
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(other_circle)).unwrap();
+        assert_eq!(circle.contribution_bitmap, 0);
+    });
+
+    client.deposit(&members[0], &circle_id);
+}
+
+#[test]
+fn deposit_inside_grace_period_has_no_penalty() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 86_400, 1);
+    let member = members[0].clone();
+
+    // One second before the grace window closes
+    env.ledger().with_mut(|li| li.timestamp += 604_800 + 86_400);
+    client.deposit(&member, &circle_id);
+
+    assert_eq!(group_reserve(&env, &contract_id), 0);
+}
+
+#[test]
+fn deposit_after_grace_period_is_penalized() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 86_400, 1);
+    let member = members[0].clone();
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 + 86_400 + 1);
+    client.deposit(&member, &circle_id);
+
+    assert_eq!(group_reserve(&env, &contract_id), 10);
+}
+
+#[test]
+fn ten_on_time_deposits_earn_the_on_time_payer_badge() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 2);
+
+    // The second member's first deposit is late, which rules the badge out for them
+    env.ledger().with_mut(|li| li.timestamp += 604_800 + 1);
+    client.deposit(&members[1], &circle_id);
+    client.deposit(&members[0], &circle_id);
+    for round in 1..10 {
+        assert_eq!(client.get_member_badges(&members[0]).len(), 0, "round {}", round);
+        start_next_round(&env, &contract_id, circle_id);
+        client.deposit(&members[0], &circle_id);
+        client.deposit(&members[1], &circle_id);
+    }
+
+    assert_eq!(client.get_member_badges(&members[0]), vec![&env, BadgeType::OnTimePayer]);
+    assert_eq!(client.get_member_badges(&members[1]).len(), 0);
+    let awarded = env.events().all().iter()
+        .filter(|(_, topics, _)| *topics == (Symbol::new(&env, "BADGE_AWARDED"), circle_id).into_val(&env))
+        .count();
+    assert_eq!(awarded, 1);
+
+    // The badge is only awarded once
+    start_next_round(&env, &contract_id, circle_id);
+    client.deposit(&members[0], &circle_id);
+    assert_eq!(client.get_member_badges(&members[0]).len(), 1);
+}
+
+#[test]
+fn deposit_a_year_after_joining_earns_the_long_term_member_badge() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 1);
+    let member = members[0].clone();
+
+    env.ledger().with_mut(|li| li.timestamp += 365 * 24 * 3600 - 1);
+    client.deposit(&member, &circle_id);
+    assert_eq!(client.get_member_badges(&member).len(), 0);
+
+    start_next_round(&env, &contract_id, circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    client.deposit(&member, &circle_id);
+    assert_eq!(client.get_member_badges(&member), vec![&env, BadgeType::LongTermMember]);
+}
+
+#[test]
+fn late_fee_stacks_for_each_cycle_missed_since_finalization() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 2);
+    let admin: Address = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Admin).unwrap());
+    assert_eq!(client.get_last_finalized_at(&circle_id), None);
+
+    client.deposit(&members[0], &circle_id);
+    client.deposit(&members[1], &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    let finalized_at = env.ledger().timestamp();
+    client.propose_finalize_round(&admin, &circle_id);
+    assert_eq!(client.get_last_finalized_at(&circle_id), Some(finalized_at));
+
+    // One cycle late costs the base 1% fee
+    env.ledger().with_mut(|li| li.timestamp = finalized_at + 604_800 + 1);
+    assert_eq!(client.simulate_deposit(&members[1], &circle_id), (1_000, 0, 10));
+
+    // Two full cycles missed: the fee is charged twice
+    env.ledger().with_mut(|li| li.timestamp = finalized_at + 2 * 604_800 + 1);
+    assert_eq!(client.simulate_deposit(&members[1], &circle_id), (1_000, 0, 20));
+    client.deposit(&members[1], &circle_id);
+    assert_eq!(group_reserve(&env, &contract_id), 20);
+
+    assert_eq!(client.try_get_last_finalized_at(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn admin_contribution_change_waits_out_the_notice_period() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 2);
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    let token_client = token::Client::new(&env, &circle.token);
+
+    assert_eq!(
+        client.try_set_contribution_amount(&members[0], &circle_id, &2_000),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_contribution_amount(&circle.creator, &circle_id, &0),
+        Err(Ok(SoroSusuError::InvalidAmount))
+    );
+
+    let proposed_at = env.ledger().timestamp();
+    client.set_contribution_amount(&circle.creator, &circle_id, &2_000);
+    assert_eq!(
+        client.try_set_contribution_amount(&circle.creator, &circle_id, &3_000),
+        Err(Ok(SoroSusuError::ChangeAlreadyPending))
+    );
+
+    // Still inside the 72-hour notice: the old amount is charged
+    env.ledger().with_mut(|li| li.timestamp = proposed_at + 72 * 3_600 - 1);
+    assert_eq!(client.simulate_deposit(&members[0], &circle_id), (1_000, 0, 0));
+    client.deposit(&members[0], &circle_id);
+    assert_eq!(token_client.balance(&members[0]), 24_000);
+
+    // Notice over: the next deposit switches the circle to the new amount
+    env.ledger().with_mut(|li| li.timestamp = proposed_at + 72 * 3_600);
+    assert_eq!(client.simulate_deposit(&members[1], &circle_id), (2_000, 0, 0));
+    client.deposit(&members[1], &circle_id);
+    assert_eq!(token_client.balance(&members[1]), 23_000);
+
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    assert_eq!(circle.contribution_amount, 2_000);
+
+    // The applied change no longer blocks a new one
+    client.set_contribution_amount(&circle.creator, &circle_id, &3_000);
+}
+
+#[test]
+fn grace_period_cannot_cover_whole_cycle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let result = client.try_create_circle(&creator, &1_000, &5, &token, &604_800, &604_800, &0, &0, &nft_contract);
+    assert_eq!(result, Err(Ok(SoroSusuError::InvalidGracePeriod)));
+}
+
+fn setup_share_limited_circle<'a>(env: &Env, max_share_bps: u32) -> (SoroSusuClient<'a>, u64, Address, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let small = Address::generate(env);
+    let large = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(env, &token).mint(&small, &10_000);
+    token::StellarAssetClient::new(env, &token).mint(&large, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &max_share_bps, &nft_contract);
+    client.join_circle(&small, &circle_id, &1);
+    client.join_circle(&large, &circle_id, &3);
+
+    (client, circle_id, small, large)
+}
+
+#[test]
+fn share_limit_rejects_dominant_member() {
+    let env = Env::default();
+    let (client, circle_id, small, large) = setup_share_limited_circle(&env, 5_000);
+
+    // 1_000 of a 4_000 pot is 25%
+    client.deposit(&small, &circle_id);
+
+    // 3_000 of a 4_000 pot is 75%
+    let result = client.try_deposit(&large, &circle_id);
+    assert_eq!(result, Err(Ok(SoroSusuError::ShareLimitExceeded)));
+}
+
+#[test]
+fn share_limit_disabled_allows_any_tier() {
+    let env = Env::default();
+    let (client, circle_id, small, large) = setup_share_limited_circle(&env, 0);
+
+    client.deposit(&small, &circle_id);
+    client.deposit(&large, &circle_id);
+}
+
+#[test]
+fn contribution_history_keeps_last_twelve_deposits() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 1);
+    let member = members[0].clone();
+
+    let mut timestamps = std::vec::Vec::new();
+    for _ in 0..12 {
+        env.ledger().with_mut(|li| li.timestamp += 3_600);
+        timestamps.push(env.ledger().timestamp());
+        client.deposit(&member, &circle_id);
+        start_next_round(&env, &contract_id, circle_id);
+    }
+
+    let history = client.get_contribution_history(&member);
+    assert_eq!(history.len(), 12);
+    assert_eq!(history.get(0).unwrap(), timestamps[0]);
+
+    // The 13th deposit evicts the oldest entry
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    let latest = env.ledger().timestamp();
+    client.deposit(&member, &circle_id);
+
+    let history = client.get_contribution_history(&member);
+    assert_eq!(history.len(), 12);
+    assert_eq!(history.get(0).unwrap(), timestamps[1]);
+    assert_eq!(history.get(11).unwrap(), latest);
+}
+
+#[test]
+fn trust_score_builds_up_with_on_time_deposits() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 1);
+    let member = members[0].clone();
+
+    assert_eq!(client.get_trust_score(&member), 0);
+    client.deposit(&member, &circle_id);
+    start_next_round(&env, &contract_id, circle_id);
+    client.deposit(&member, &circle_id);
+    assert_eq!(client.get_trust_score(&member), 10);
+}
+
+#[test]
+fn trust_score_decays_on_late_deposits_and_stops_at_zero() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 1);
+    let member = members[0].clone();
+
+    for _ in 0..3 {
+        client.deposit(&member, &circle_id);
+        start_next_round(&env, &contract_id, circle_id);
+    }
+    assert_eq!(client.get_trust_score(&member), 15);
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 + 1);
+    client.deposit(&member, &circle_id);
+    start_next_round(&env, &contract_id, circle_id);
+    assert_eq!(client.get_trust_score(&member), 5);
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 + 1);
+    client.deposit(&member, &circle_id);
+    assert_eq!(client.get_trust_score(&member), 0);
+}
+
+#[test]
+fn trust_score_is_capped_at_one_hundred() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 1);
+    let member = members[0].clone();
+
+    // 19 on-time deposits reach 95; the 20th lands exactly on the cap
+    for _ in 0..19 {
+        client.deposit(&member, &circle_id);
+        start_next_round(&env, &contract_id, circle_id);
+    }
+    assert_eq!(client.get_trust_score(&member), 95);
+
+    client.deposit(&member, &circle_id);
+    start_next_round(&env, &contract_id, circle_id);
+    assert_eq!(client.get_trust_score(&member), 100);
+
+    client.deposit(&member, &circle_id);
+    assert_eq!(client.get_trust_score(&member), 100);
+}
+
+fn fund_payer(env: &Env, contract_id: &Address, circle_id: u64, amount: i128) -> (Address, token::Client<'static>) {
+    let circle_token = env.as_contract(contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.token
+    });
+    let payer = Address::generate(env);
+    token::StellarAssetClient::new(env, &circle_token).mint(&payer, &amount);
+    (payer, token::Client::new(env, &circle_token))
+}
+
+#[test]
+fn bulk_deposit_pays_for_every_member() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 3);
+    let (payer, token_client) = fund_payer(&env, &contract_id, circle_id, 3_000);
+
+    client.bulk_deposit(&payer, &circle_id, &vec![&env, members[0].clone(), members[1].clone(), members[2].clone()]);
+
+    assert_eq!(token_client.balance(&payer), 0);
+    assert_eq!(token_client.balance(&members[0]), 25_000);
+    for member in &members {
+        assert_eq!(client.get_contribution_history(member).len(), 1);
+    }
+}
+
+#[test]
+fn bulk_deposit_reverts_when_payer_runs_out() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 3);
+    // Covers two of the three contributions
+    let (payer, token_client) = fund_payer(&env, &contract_id, circle_id, 2_000);
+
+    let result = client.try_bulk_deposit(&payer, &circle_id, &vec![&env, members[0].clone(), members[1].clone(), members[2].clone()]);
+    assert!(result.is_err());
+
+    assert_eq!(token_client.balance(&payer), 2_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    for member in &members {
+        assert_eq!(client.get_contribution_history(member).len(), 0);
+    }
+}
+
+#[test]
+fn protocol_volume_accumulates_across_circles() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let saver = Address::generate(&env);
+    let big_saver = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&saver, &10_000);
+    token::StellarAssetClient::new(&env, &token).mint(&big_saver, &10_000);
+
+    client.init(&admin);
+    assert_eq!(client.get_protocol_volume(), 0);
+
+    let small_circle = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    let large_circle = client.create_circle(&creator, &2_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&saver, &small_circle, &1);
+    client.join_circle(&big_saver, &large_circle, &1);
+
+    client.deposit(&saver, &small_circle);
+    start_next_round(&env, &contract_id, small_circle);
+    client.deposit(&saver, &small_circle);
+    assert_eq!(client.get_protocol_volume(), 2_000);
+
+    client.deposit(&big_saver, &large_circle);
+    assert_eq!(client.get_protocol_volume(), 4_000);
+}
+
+#[test]
+fn simulate_deposit_previews_what_deposit_charges() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let bronze = Address::generate(&env);
+    let silver = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&silver, &10_000);
+
+    client.init(&admin);
+    // 2% insurance fee, 1% default late fee
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &200, &0, &nft_contract);
+    client.join_circle(&bronze, &circle_id, &1);
+    client.join_circle(&silver, &circle_id, &2);
+
+    assert_eq!(client.simulate_deposit(&bronze, &circle_id), (1_000, 20, 0));
+    assert_eq!(client.simulate_deposit(&silver, &circle_id), (2_000, 40, 0));
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 + 1);
+    assert_eq!(client.simulate_deposit(&bronze, &circle_id), (1_000, 20, 10));
+    let (base, insurance, penalty) = client.simulate_deposit(&silver, &circle_id);
+    assert_eq!((base, insurance, penalty), (2_000, 40, 20));
+
+    // The preview matches what deposit actually pulls
+    client.deposit(&silver, &circle_id);
+    let spent = 10_000 - token::Client::new(&env, &token).balance(&silver);
+    assert_eq!(spent as u64, base + insurance + penalty);
+
+    assert_eq!(client.try_simulate_deposit(&creator, &circle_id), Err(Ok(SoroSusuError::NotAMember)));
+}
+
+#[test]
+fn second_deposit_in_same_round_is_rejected() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 2);
+
+    client.deposit(&members[0], &circle_id);
+    assert_eq!(client.try_deposit(&members[0], &circle_id), Err(Ok(SoroSusuError::AlreadyContributed)));
+
+    // Only the first deposit was taken
+    assert_eq!(client.get_contribution_history(&members[0]).len(), 1);
+    assert_eq!(client.get_protocol_volume(), 1_000);
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    assert_eq!(circle.contribution_bitmap, 1);
+}
+
+#[test]
+fn deposit_with_note_behaves_like_deposit() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_token_circle(&env, 0, 2);
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    let token_client = token::Client::new(&env, &circle.token);
+
+    client.deposit(&members[0], &circle_id);
+    let note = Symbol::new(&env, "INV_2024_0042");
+    client.deposit_with_note(&members[1], &circle_id, &note);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "DEPOSIT_WITH_NOTE"), circle_id).into_val(&env));
+    assert_eq!(<(Address, Symbol)>::from_val(&env, &data), (members[1].clone(), note.clone()));
+
+    assert_eq!(token_client.balance(&members[0]), token_client.balance(&members[1]));
+    assert_eq!(client.get_contribution_history(&members[1]), client.get_contribution_history(&members[0]));
+    assert_eq!(client.get_protocol_volume(), 2_000);
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    assert_eq!(circle.contribution_bitmap, 0b11);
+
+    assert_eq!(
+        client.try_deposit_with_note(&members[1], &circle_id, &note),
+        Err(Ok(SoroSusuError::AlreadyContributed))
+    );
+}
@@ -0,0 +1,261 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, MockAuth, MockAuthInvoke},
+    vec, Address, Bytes, Env, IntoVal, Symbol,
+};
+use sorosusu_contracts::{CircleCategory, DataKey, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+fn setup<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, Address, u64, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let member = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    (client, admin, creator, circle_id, member)
+}
+
+#[test]
+fn create_circle_rejects_invalid_parameters() {
+    let env = Env::default();
+    let (client, _admin, creator, _circle_id, _member) = setup(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    assert_eq!(
+        client.try_create_circle(&creator, &1_000, &65, &token, &604_800, &0, &0, &0, &nft_contract),
+        Err(Ok(SoroSusuError::MaxMembersExceeded))
+    );
+    assert_eq!(
+        client.try_create_circle(&creator, &1_000, &4, &token, &604_800, &0, &10_001, &0, &nft_contract),
+        Err(Ok(SoroSusuError::InvalidBasisPoints))
+    );
+    assert_eq!(
+        client.try_create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &10_001, &nft_contract),
+        Err(Ok(SoroSusuError::InvalidBasisPoints))
+    );
+}
+
+#[test]
+fn create_circle_reports_exhausted_ids() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&Address::generate(&env));
+    env.as_contract(&contract_id, || env.storage().instance().set(&DataKey::CircleCount, &u64::MAX));
+    assert_eq!(
+        client.try_create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract),
+        Err(Ok(SoroSusuError::InvalidOperation))
+    );
+}
+
+#[test]
+fn create_circle_requires_the_creators_signature() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+    client.init(&Address::generate(&env));
+
+    // Nobody can open a circle, and list it under the creator's name, without the creator signing
+    let result = client
+        .mock_auths(&[])
+        .try_create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    assert!(result.is_err());
+    assert_eq!(client.get_circles_by_creator(&creator).len(), 0);
+
+    let circle_id = client
+        .mock_auths(&[MockAuth {
+            address: &creator,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "create_circle",
+                args: (creator.clone(), 1_000u64, 4u32, token.clone(), 604_800u64, 0u64, 0u32, 0u32, nft_contract.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    // Cloning signs once, for the clone call itself
+    let cloned = client
+        .mock_auths(&[MockAuth {
+            address: &creator,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "clone_circle",
+                args: (creator.clone(), circle_id).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .clone_circle(&creator, &circle_id);
+    assert_eq!(client.get_circles_by_creator(&creator).len(), 2);
+    assert_ne!(cloned, circle_id);
+}
+
+#[test]
+fn join_circle_reports_membership_errors() {
+    let env = Env::default();
+    let (client, _admin, _creator, circle_id, member) = setup(&env);
+    let newcomer = Address::generate(&env);
+
+    assert_eq!(client.try_join_circle(&newcomer, &99, &1), Err(Ok(SoroSusuError::CircleNotFound)));
+    assert_eq!(client.try_join_circle(&member, &circle_id, &1), Err(Ok(SoroSusuError::AlreadyMember)));
+    assert_eq!(client.try_join_circle(&newcomer, &circle_id, &0), Err(Ok(SoroSusuError::InvalidTier)));
+    assert_eq!(
+        client.try_join_circle_with_referrer(&newcomer, &circle_id, &1, &Some(newcomer.clone())),
+        Err(Ok(SoroSusuError::InvalidReferrer))
+    );
+
+    client.join_circle(&newcomer, &circle_id, &1);
+    assert_eq!(client.try_join_circle(&Address::generate(&env), &circle_id, &1), Err(Ok(SoroSusuError::CircleFull)));
+}
+
+#[test]
+fn member_actions_require_membership() {
+    let env = Env::default();
+    let (client, _admin, _creator, circle_id, member) = setup(&env);
+    let outsider = Address::generate(&env);
+
+    assert_eq!(client.try_deposit(&outsider, &circle_id), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(client.try_request_exit(&outsider, &circle_id), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(client.try_vote_penalty_change(&outsider, &circle_id), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(client.try_propose_penalty_change(&member, &circle_id, &10_001), Err(Ok(SoroSusuError::InvalidBasisPoints)));
+    assert_eq!(client.try_propose_contribution_change(&member, &circle_id, &0), Err(Ok(SoroSusuError::InvalidAmount)));
+    assert_eq!(
+        client.try_propose_address_change(&member, &circle_id, &member, &member),
+        Err(Ok(SoroSusuError::InvalidAddress))
+    );
+    assert_eq!(client.try_fill_vacancy(&outsider, &circle_id, &member), Err(Ok(SoroSusuError::NoPendingExit)));
+
+    client.request_exit(&member, &circle_id);
+    assert_eq!(client.try_deposit(&member, &circle_id), Err(Ok(SoroSusuError::MemberNotActive)));
+}
+
+#[test]
+fn privileged_calls_reject_other_callers() {
+    let env = Env::default();
+    let (client, admin, creator, circle_id, member) = setup(&env);
+
+    assert_eq!(client.try_set_lending_pool(&creator, &creator, &1), Err(Ok(SoroSusuError::Unauthorized)));
+    assert_eq!(client.try_eject_member(&member, &circle_id, &member), Err(Ok(SoroSusuError::Unauthorized)));
+    assert_eq!(client.try_propose_duration_change(&member, &circle_id, &3_600), Err(Ok(SoroSusuError::Unauthorized)));
+    assert_eq!(
+        client.try_set_admin_list(&admin, &vec![&env, admin.clone()], &2),
+        Err(Ok(SoroSusuError::InvalidThreshold))
+    );
+    assert_eq!(
+        client.try_deposit_to_yield_pool(&creator, &circle_id, &500),
+        Err(Ok(SoroSusuError::LendingPoolNotConfigured))
+    );
+    assert_eq!(client.try_distribute_group_reserve(&creator, &circle_id), Err(Ok(SoroSusuError::ReserveEmpty)));
+    assert_eq!(client.try_approve_operation(&admin, &42, &0), Err(Ok(SoroSusuError::OperationNotFound)));
+}
+
+#[test]
+fn admin_calls_require_initialization() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+    let caller = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    assert_eq!(client.try_set_lending_pool(&caller, &caller, &1), Err(Ok(SoroSusuError::NotInitialized)));
+}
+
+#[test]
+fn unknown_circle_id_is_reported_consistently() {
+    let env = Env::default();
+    let (client, admin, creator, _circle_id, member) = setup(&env);
+    let other = Address::generate(&env);
+    let missing = 99u64;
+    let not_found = Some(Ok(SoroSusuError::CircleNotFound));
+
+    assert_eq!(client.try_clone_circle(&creator, &missing).err(), not_found);
+    assert_eq!(client.try_join_circle(&other, &missing, &1).err(), not_found);
+    assert_eq!(client.try_set_invite_only(&creator, &missing, &true).err(), not_found);
+    assert_eq!(client.try_issue_invite(&creator, &missing, &other).err(), not_found);
+    assert_eq!(client.try_set_circle_category(&creator, &missing, &CircleCategory::Business).err(), not_found);
+    assert_eq!(client.try_set_circle_description(&creator, &missing, &Symbol::new(&env, "x"), &Bytes::new(&env)).err(), not_found);
+    assert_eq!(client.try_revoke_invite(&creator, &missing, &other).err(), not_found);
+    assert_eq!(client.try_deposit(&member, &missing).err(), not_found);
+    assert_eq!(client.try_simulate_deposit(&member, &missing).err(), not_found);
+    assert_eq!(client.try_bulk_deposit(&creator, &missing, &vec![&env, member.clone()]).err(), not_found);
+    assert_eq!(client.try_distribute_group_reserve(&creator, &missing).err(), not_found);
+    assert_eq!(client.try_set_circle_yield_strategy(&creator, &missing, &other).err(), not_found);
+    assert_eq!(client.try_deposit_to_yield_pool(&creator, &missing, &500).err(), not_found);
+    assert_eq!(client.try_prepare_payout_liquidity(&creator, &missing).err(), not_found);
+    assert_eq!(client.try_get_yield_earnings(&missing).err(), not_found);
+    assert_eq!(client.try_is_yield_active(&missing).err(), not_found);
+    assert_eq!(client.try_get_insurance_fund(&missing).err(), not_found);
+    assert_eq!(client.try_trigger_insurance_coverage(&creator, &missing, &member).err(), not_found);
+    assert_eq!(client.try_propose_penalty_change(&member, &missing, &500).err(), not_found);
+    assert_eq!(client.try_propose_duration_change(&creator, &missing, &86_400).err(), not_found);
+    assert_eq!(client.try_set_contribution_amount(&creator, &missing, &2_000).err(), not_found);
+    assert_eq!(client.try_cancel_duration_change(&creator, &missing).err(), not_found);
+    assert_eq!(client.try_vote_penalty_change(&member, &missing).err(), not_found);
+    assert_eq!(client.try_admin_set_late_fee_bps(&creator, &missing, &500).err(), not_found);
+    assert_eq!(client.try_propose_contribution_change(&member, &missing, &2_000).err(), not_found);
+    assert_eq!(client.try_vote_contribution_change(&member, &missing).err(), not_found);
+    assert_eq!(client.try_propose_insurance_fee_change(&member, &missing, &500).err(), not_found);
+    assert_eq!(client.try_vote_insurance_fee_change(&member, &missing).err(), not_found);
+    assert_eq!(client.try_propose_deadline_extension(&member, &missing, &3_600).err(), not_found);
+    assert_eq!(client.try_vote_deadline_extension(&member, &missing).err(), not_found);
+    assert_eq!(client.try_propose_max_members_change(&member, &missing, &8).err(), not_found);
+    assert_eq!(client.try_vote_max_members_change(&member, &missing).err(), not_found);
+    assert_eq!(client.try_propose_address_change(&member, &missing, &member, &other).err(), not_found);
+    assert_eq!(client.try_vote_for_recovery(&member, &missing).err(), not_found);
+    assert_eq!(client.try_cancel_expired_recovery(&missing).err(), not_found);
+    assert_eq!(client.try_reclaim_nft(&member, &other, &missing).err(), not_found);
+    assert_eq!(client.try_set_refund_ejected_members(&creator, &missing, &true).err(), not_found);
+    assert_eq!(client.try_set_quorum_threshold(&creator, &missing, &6_700).err(), not_found);
+    assert_eq!(client.try_update_yield_idle_threshold(&creator, &missing, &7_200).err(), not_found);
+    assert_eq!(client.try_set_priority_ordering(&creator, &missing, &true).err(), not_found);
+    assert_eq!(client.try_get_payout_order(&missing).err(), not_found);
+    assert_eq!(client.try_freeze_member(&creator, &missing, &member).err(), not_found);
+    assert_eq!(client.try_unfreeze_member(&creator, &missing, &member).err(), not_found);
+    assert_eq!(client.try_eject_member(&creator, &missing, &member).err(), not_found);
+    assert_eq!(client.try_sweep_inactive_members(&creator, &missing).err(), not_found);
+    assert_eq!(client.try_request_exit(&member, &missing).err(), not_found);
+    assert_eq!(client.try_fill_vacancy(&other, &missing, &member).err(), not_found);
+    assert_eq!(client.try_request_tier_change(&member, &missing, &2).err(), not_found);
+    assert_eq!(client.try_approve_tier_change(&creator, &missing, &member).err(), not_found);
+    assert_eq!(client.try_claim_pot(&member, &missing).err(), not_found);
+    assert_eq!(client.try_cancel_round(&creator, &missing).err(), not_found);
+    assert_eq!(client.try_simulate_payout(&missing).err(), not_found);
+    assert_eq!(client.try_propose_eject_member(&admin, &missing, &member).err(), not_found);
+    assert_eq!(client.try_propose_finalize_round(&admin, &missing).err(), not_found);
+    assert_eq!(client.try_propose_trigger_insurance(&admin, &missing, &member).err(), not_found);
+    assert_eq!(client.try_admin_zero_yield_deposited(&admin, &missing).err(), not_found);
+    assert_eq!(client.try_get_pending_operations(&missing).err(), not_found);
+}
@@ -0,0 +1,160 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Events, Ledger},
+    token, Address, Env, FromVal, IntoVal, Symbol,
+};
+use sorosusu_contracts::{DataKey, GroupHealthUpdateEvent, Member, MemberStatus, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+// 100% insurance fee so the active members' deposits can cover the frozen seat every round
+fn setup_frozen_circle<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, Address, Address, u64, std::vec::Vec<Address>) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &1_000, &0, &nft_contract);
+
+    // Enough insurance to cover the frozen member for a couple of rounds
+    token::StellarAssetClient::new(env, &token).mint(&contract_id, &5_000);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &5_000u64);
+    });
+
+    let mut members = std::vec::Vec::new();
+    for _ in 0..4 {
+        let member = Address::generate(env);
+        token::StellarAssetClient::new(env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    client.freeze_member(&creator, &circle_id, &members[3]);
+
+    (client, contract_id, admin, creator, circle_id, members)
+}
+
+fn member_status(env: &Env, contract_id: &Address, member: &Address) -> MemberStatus {
+    env.as_contract(contract_id, || {
+        let member: Member = env.storage().instance().get(&DataKey::Member(member.clone())).unwrap();
+        member.status
+    })
+}
+
+#[test]
+fn frozen_member_cannot_deposit() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, _creator, circle_id, members) = setup_frozen_circle(&env);
+
+    assert_eq!(client.try_deposit(&members[3], &circle_id), Err(Ok(SoroSusuError::MemberNotActive)));
+}
+
+#[test]
+fn frozen_member_cannot_vote() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, _creator, circle_id, members) = setup_frozen_circle(&env);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    client.propose_penalty_change(&members[0], &circle_id, &500);
+    assert_eq!(client.try_vote_penalty_change(&members[3], &circle_id), Err(Ok(SoroSusuError::MemberNotActive)));
+}
+
+#[test]
+fn unfrozen_member_can_deposit_again() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, creator, circle_id, members) = setup_frozen_circle(&env);
+
+    client.unfreeze_member(&creator, &circle_id, &members[3]);
+    client.deposit(&members[3], &circle_id);
+
+    assert_eq!(client.try_unfreeze_member(&creator, &circle_id, &members[3]), Err(Ok(SoroSusuError::MemberNotFrozen)));
+}
+
+#[test]
+fn member_frozen_for_two_rounds_awaits_replacement() {
+    let env = Env::default();
+    let (client, contract_id, admin, creator, circle_id, members) = setup_frozen_circle(&env);
+
+    for round in 0..2 {
+        for member in &members[..3] {
+            client.deposit(member, &circle_id);
+        }
+        client.trigger_insurance_coverage(&creator, &circle_id, &members[3]);
+        client.propose_finalize_round(&admin, &circle_id);
+
+        if round == 0 {
+            assert_eq!(member_status(&env, &contract_id, &members[3]), MemberStatus::Frozen);
+
+            // Claiming the pot opens the next round
+            env.ledger().with_mut(|li| li.timestamp += 86_400);
+            client.claim_pot(&members[0], &circle_id);
+        }
+    }
+
+    assert_eq!(member_status(&env, &contract_id, &members[3]), MemberStatus::AwaitingReplacement);
+
+    // A forced exit cannot be taken back by the frozen member
+    assert_eq!(client.try_cancel_exit_request(&members[3], &circle_id), Err(Ok(SoroSusuError::Unauthorized)));
+
+    // The seat can now be filled like a voluntary exit
+    let replacement = Address::generate(&env);
+    client.fill_vacancy(&replacement, &circle_id, &members[3]);
+    assert_eq!(client.get_member_circle(&replacement), circle_id);
+}
+
+#[test]
+fn status_counts_and_health_exclude_ejected_and_frozen_members() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, creator, circle_id, members) = setup_frozen_circle(&env);
+
+    client.eject_member(&creator, &circle_id, &members[2]);
+    assert_eq!(client.get_member_status_counts(&circle_id), (2, 0, 1, 1));
+
+    client.deposit(&members[0], &circle_id);
+
+    let (_, _, data) = env.events().all().iter()
+        .filter(|(_, topics, _)| *topics == (Symbol::new(&env, "GROUP_HEALTH"), circle_id).into_val(&env))
+        .last()
+        .unwrap();
+    let health = GroupHealthUpdateEvent::from_val(&env, &data);
+    assert_eq!(health.active_members, 2);
+    assert_eq!(health.missed_payments, 1);
+    assert_eq!(health.trust_score, 50);
+
+    client.request_exit(&members[1], &circle_id);
+    assert_eq!(client.get_member_status_counts(&circle_id), (1, 1, 1, 1));
+    assert_eq!(client.try_get_member_status_counts(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn creators_cannot_freeze_members_of_other_circles() {
+    let env = Env::default();
+    let (client, contract_id, _admin, _creator, _circle_id, members) = setup_frozen_circle(&env);
+
+    let other_creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = Address::generate(&env);
+    let other_circle = client.create_circle(&other_creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    assert_eq!(client.try_freeze_member(&other_creator, &other_circle, &members[0]), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(client.try_unfreeze_member(&other_creator, &other_circle, &members[3]), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(member_status(&env, &contract_id, &members[0]), MemberStatus::Active);
+    assert_eq!(member_status(&env, &contract_id, &members[3]), MemberStatus::Frozen);
+}
@@ -0,0 +1,697 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Ledger},
+    token, vec, Address, Env,
+};
+use sorosusu_contracts::{CircleInfo, DataKey, ProposalType, SoroSusu, SoroSusuClient, SoroSusuError, VoterTurnout};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+#[test]
+fn contribution_change_applies_after_majority_vote() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let member3 = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    let token_admin_client = token::StellarAssetClient::new(&env, &token);
+    token_admin_client.mint(&member1, &10_000);
+    token_admin_client.mint(&member2, &10_000);
+    token_admin_client.mint(&member3, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    client.join_circle(&member1, &circle_id, &1);
+    client.join_circle(&member2, &circle_id, &1);
+    client.join_circle(&member3, &circle_id, &1);
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    // Round under the original amount
+    client.deposit(&member1, &circle_id);
+
+    // Proposal auto-votes for proposer: 1/3 is not a majority
+    client.propose_contribution_change(&member1, &circle_id, &2_000);
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.contribution_amount, 1_000);
+        assert_eq!(circle.proposed_contribution_amount, 2_000);
+    });
+
+    // Second vote: 2/3 passes, but the round in progress keeps the amount it started with
+    client.vote_contribution_change(&member2, &circle_id);
+    client.deposit(&member2, &circle_id);
+    client.deposit(&member3, &circle_id);
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.contribution_amount, 1_000);
+    });
+
+    client.propose_finalize_round(&admin, &circle_id);
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.contribution_amount, 2_000);
+        assert_eq!(circle.proposed_contribution_amount, 0);
+        assert_eq!(circle.contribution_votes_bitmap, 0);
+    });
+
+    // Deposits for the next round use the new amount
+    client.deposit(&member2, &circle_id);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&member1), 9_000);
+    assert_eq!(token_client.balance(&member2), 7_000);
+    assert_eq!(token_client.balance(&member3), 9_000);
+    assert_eq!(token_client.balance(&contract_id), 5_000);
+}
+
+#[test]
+fn contribution_proposal_is_scoped_to_the_voters_circle_and_expires() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+    let nft_contract = env.register_contract(None, MockNft);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let other_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    assert_eq!(client.try_propose_contribution_change(&members[0], &other_circle, &2_000), Err(Ok(SoroSusuError::NotAMember)));
+
+    client.propose_contribution_change(&members[0], &circle_id, &2_000);
+    assert_eq!(client.try_vote_contribution_change(&members[1], &other_circle), Err(Ok(SoroSusuError::NotAMember)));
+    env.ledger().with_mut(|li| li.timestamp += 7 * 24 * 3600 + 1);
+    assert_eq!(client.try_vote_contribution_change(&members[1], &circle_id), Err(Ok(SoroSusuError::ProposalExpired)));
+    assert_eq!(circle_info(&env, &contract_id, circle_id).contribution_amount, 1_000);
+}
+
+#[test]
+fn contribution_vote_requires_active_proposal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member1, &circle_id, &1);
+
+    let result = client.try_vote_contribution_change(&member1, &circle_id);
+    assert_eq!(result, Err(Ok(SoroSusuError::NoActiveProposal)));
+}
+
+#[test]
+fn admin_override_breaks_deadlocked_penalty_vote() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member1, &circle_id, &1);
+    client.join_circle(&member2, &circle_id, &1);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    // 1 of 2 votes: member2 never shows up, so the proposal is stuck
+    client.propose_penalty_change(&member1, &circle_id, &500);
+
+    client.admin_set_late_fee_bps(&creator, &circle_id, &300);
+
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.late_fee_bps, 300);
+        assert_eq!(circle.proposed_late_fee_bps, 0);
+        assert_eq!(circle.proposal_votes_bitmap, 0);
+    });
+
+    // The stale proposal can no longer be voted in
+    assert!(client.try_vote_penalty_change(&member2, &circle_id).is_err());
+
+    // Protocol admin can override too, members cannot
+    client.admin_set_late_fee_bps(&admin, &circle_id, &200);
+    assert!(client.try_admin_set_late_fee_bps(&member1, &circle_id, &0).is_err());
+    assert!(client.try_admin_set_late_fee_bps(&admin, &circle_id, &10_001).is_err());
+}
+
+#[test]
+fn penalty_vote_needs_strictly_more_than_half() {
+    // (circle size, votes needed to pass)
+    for (size, needed) in [(2u32, 2u32), (3, 2), (4, 3), (5, 3), (6, 4)] {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, SoroSusu);
+        let client = SoroSusuClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let token = Address::generate(&env);
+        let nft_contract = env.register_contract(None, MockNft);
+
+        env.mock_all_auths();
+
+        client.init(&admin);
+        let circle_id = client.create_circle(&creator, &1_000, &size, &token, &604_800, &0, &0, &0, &nft_contract);
+        let mut members = std::vec::Vec::new();
+        for _ in 0..size {
+            let member = Address::generate(&env);
+            client.join_circle(&member, &circle_id, &1);
+            members.push(member);
+        }
+        env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+        client.propose_penalty_change(&members[0], &circle_id, &500);
+        for voter in 1..needed {
+            assert_ne!(circle_info(&env, &contract_id, circle_id).late_fee_bps, 500, "size {} passed early", size);
+            client.vote_penalty_change(&members[voter as usize], &circle_id);
+        }
+        assert_eq!(circle_info(&env, &contract_id, circle_id).late_fee_bps, 500, "size {} did not pass", size);
+    }
+}
+
+#[test]
+fn penalty_vote_follows_configured_quorum() {
+    // (quorum in bps, votes needed out of six members)
+    for (quorum_bps, needed) in [(5_001u32, 4u32), (6_700, 5), (10_000, 6)] {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, SoroSusu);
+        let client = SoroSusuClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let token = Address::generate(&env);
+        let nft_contract = env.register_contract(None, MockNft);
+
+        env.mock_all_auths();
+
+        client.init(&admin);
+        let circle_id = client.create_circle(&creator, &1_000, &6, &token, &604_800, &0, &0, &0, &nft_contract);
+        client.set_quorum_threshold(&creator, &circle_id, &quorum_bps);
+        let mut members = std::vec::Vec::new();
+        for _ in 0..6 {
+            let member = Address::generate(&env);
+            client.join_circle(&member, &circle_id, &1);
+            members.push(member);
+        }
+        env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+        client.propose_penalty_change(&members[0], &circle_id, &500);
+        for voter in 1..needed {
+            assert_ne!(circle_info(&env, &contract_id, circle_id).late_fee_bps, 500, "quorum {} passed early", quorum_bps);
+            client.vote_penalty_change(&members[voter as usize], &circle_id);
+        }
+        assert_eq!(circle_info(&env, &contract_id, circle_id).late_fee_bps, 500, "quorum {} did not pass", quorum_bps);
+    }
+}
+
+#[test]
+fn quorum_is_fixed_once_members_join() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    for quorum_bps in [5_000u32, 10_001] {
+        assert_eq!(
+            client.try_set_quorum_threshold(&creator, &circle_id, &quorum_bps),
+            Err(Ok(SoroSusuError::InvalidBasisPoints))
+        );
+    }
+    assert_eq!(client.try_set_quorum_threshold(&admin, &circle_id, &6_700), Err(Ok(SoroSusuError::Unauthorized)));
+
+    client.join_circle(&Address::generate(&env), &circle_id, &1);
+    assert_eq!(client.try_set_quorum_threshold(&creator, &circle_id, &6_700), Err(Ok(SoroSusuError::InvalidOperation)));
+}
+
+#[test]
+fn proposals_wait_until_the_circle_is_a_cycle_old() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let created_at = env.ledger().timestamp();
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = created_at + 604_799);
+    assert_eq!(client.get_circle_age(&circle_id), 604_799);
+    let too_young = Err(Ok(SoroSusuError::InvalidOperation));
+    assert_eq!(client.try_propose_penalty_change(&member, &circle_id, &500), too_young);
+    assert_eq!(client.try_propose_insurance_fee_change(&member, &circle_id, &500), too_young);
+    assert_eq!(client.try_propose_duration_change(&creator, &circle_id, &86_400), too_young);
+    assert_eq!(client.try_propose_contribution_change(&member, &circle_id, &2_000), too_young);
+
+    env.ledger().with_mut(|li| li.timestamp = created_at + 604_800);
+    assert_eq!(client.get_circle_age(&circle_id), 604_800);
+    client.propose_penalty_change(&member, &circle_id, &500);
+    client.propose_insurance_fee_change(&member, &circle_id, &500);
+    client.propose_duration_change(&creator, &circle_id, &86_400);
+    client.propose_contribution_change(&member, &circle_id, &2_000);
+
+    assert_eq!(client.try_get_circle_age(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn penalty_proposal_expires_after_a_week() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+
+    let late_fee_bps = circle_info(&env, &contract_id, circle_id).late_fee_bps;
+    client.propose_penalty_change(&members[0], &circle_id, &500);
+    let expiry: u64 = env.as_contract(&contract_id, || {
+        env.storage().instance().get(&DataKey::ProposalExpiry(circle_id)).unwrap()
+    });
+    assert_eq!(expiry, env.ledger().timestamp() + 7 * 24 * 3600);
+
+    env.ledger().with_mut(|li| li.timestamp = expiry + 1);
+    assert_eq!(
+        client.try_vote_penalty_change(&members[1], &circle_id),
+        Err(Ok(SoroSusuError::ProposalExpired))
+    );
+    assert_eq!(circle_info(&env, &contract_id, circle_id).late_fee_bps, late_fee_bps);
+}
+
+#[test]
+fn penalty_votes_from_other_circles_are_rejected() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+    let creator = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let other_circle = client.create_circle(&creator, &1_000, &4, &Address::generate(&env), &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&outsider, &other_circle, &1);
+
+    let not_a_member = Err(Ok(SoroSusuError::NotAMember));
+    assert_eq!(client.try_propose_penalty_change(&outsider, &circle_id, &500), not_a_member);
+
+    client.propose_penalty_change(&members[0], &circle_id, &500);
+    assert_eq!(client.try_vote_penalty_change(&outsider, &circle_id), not_a_member);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).proposal_votes_bitmap, 1);
+}
+
+#[test]
+fn live_penalty_proposal_cannot_be_replaced() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+
+    client.propose_penalty_change(&members[0], &circle_id, &500);
+    env.ledger().with_mut(|li| li.timestamp += 7 * 24 * 3600);
+    assert_eq!(
+        client.try_propose_penalty_change(&members[1], &circle_id, &300),
+        Err(Ok(SoroSusuError::ChangeAlreadyPending))
+    );
+
+    // The original vote carries on untouched
+    client.vote_penalty_change(&members[1], &circle_id);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).late_fee_bps, 500);
+}
+
+#[test]
+fn expired_penalty_proposal_can_be_replaced() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+
+    client.propose_penalty_change(&members[0], &circle_id, &500);
+    env.ledger().with_mut(|li| li.timestamp += 7 * 24 * 3600 + 1);
+
+    // The new proposal starts from a clean slate: only the proposer's vote counts
+    client.propose_penalty_change(&members[1], &circle_id, &300);
+    let circle = circle_info(&env, &contract_id, circle_id);
+    assert_eq!(circle.proposed_late_fee_bps, 300);
+    assert_eq!(circle.proposal_votes_bitmap.count_ones(), 1);
+
+    client.vote_penalty_change(&members[2], &circle_id);
+    let circle = circle_info(&env, &contract_id, circle_id);
+    assert_eq!(circle.late_fee_bps, 300);
+    assert_eq!(circle.proposed_late_fee_bps, 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::ProposalExpiry(circle_id)));
+    });
+}
+
+#[test]
+fn penalty_history_logs_votes_and_overrides() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+    let creator = circle_info(&env, &contract_id, circle_id).creator;
+
+    // A proposal that has not passed yet is not logged
+    client.propose_penalty_change(&members[0], &circle_id, &500);
+    assert_eq!(client.get_penalty_history(&circle_id).len(), 0);
+    let first_at = env.ledger().timestamp();
+    client.vote_penalty_change(&members[1], &circle_id);
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    let second_at = env.ledger().timestamp();
+    client.propose_penalty_change(&members[2], &circle_id, &700);
+    client.vote_penalty_change(&members[0], &circle_id);
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    let override_at = env.ledger().timestamp();
+    client.admin_set_late_fee_bps(&creator, &circle_id, &300);
+
+    assert_eq!(
+        client.get_penalty_history(&circle_id),
+        vec![&env, (500u32, first_at), (700u32, second_at), (300u32, override_at)]
+    );
+    assert_eq!(client.try_get_penalty_history(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+fn turnout(proposed_value: u32, votes_cast: u32, approved: bool, resolved_at: u64) -> VoterTurnout {
+    VoterTurnout { proposal_type: ProposalType::LateFee, proposed_value, votes_cast, member_count: 3, approved, resolved_at }
+}
+
+#[test]
+fn governance_history_records_each_resolution() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+    let creator = circle_info(&env, &contract_id, circle_id).creator;
+
+    // Approved by two of three votes
+    client.propose_penalty_change(&members[0], &circle_id, &500);
+    client.vote_penalty_change(&members[1], &circle_id);
+    let approved_at = env.ledger().timestamp();
+
+    // Left to expire, then replaced by a fresh proposal
+    client.propose_penalty_change(&members[0], &circle_id, &700);
+    env.ledger().with_mut(|li| li.timestamp += 7 * 24 * 3600 + 1);
+    let expired_at = env.ledger().timestamp();
+    client.propose_penalty_change(&members[1], &circle_id, &300);
+
+    // Cut short by an admin override
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    let overridden_at = env.ledger().timestamp();
+    client.admin_set_late_fee_bps(&creator, &circle_id, &200);
+
+    assert_eq!(
+        client.get_governance_history(&circle_id),
+        vec![
+            &env,
+            turnout(500, 2, true, approved_at),
+            turnout(700, 1, false, expired_at),
+            turnout(300, 1, false, overridden_at),
+        ]
+    );
+    assert_eq!(client.try_get_governance_history(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn pending_duration_change_cannot_be_overwritten() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, _members) = setup_resizable_circle(&env, 4);
+    let creator = circle_info(&env, &contract_id, circle_id).creator;
+
+    client.propose_duration_change(&creator, &circle_id, &86_400);
+    let effective_at = circle_info(&env, &contract_id, circle_id).duration_change_effective_at;
+
+    assert_eq!(
+        client.try_propose_duration_change(&creator, &circle_id, &172_800),
+        Err(Ok(SoroSusuError::ChangeAlreadyPending))
+    );
+    let circle = circle_info(&env, &contract_id, circle_id);
+    assert_eq!(circle.pending_cycle_duration, 86_400);
+    assert_eq!(circle.duration_change_effective_at, effective_at);
+
+    // Once the notice period is over a new change may be queued
+    env.ledger().with_mut(|li| li.timestamp = effective_at);
+    client.propose_duration_change(&creator, &circle_id, &172_800);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).pending_cycle_duration, 172_800);
+}
+
+#[test]
+fn pending_duration_change_can_be_cancelled() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+    let creator = circle_info(&env, &contract_id, circle_id).creator;
+
+    assert_eq!(
+        client.try_cancel_duration_change(&creator, &circle_id),
+        Err(Ok(SoroSusuError::NoActiveProposal))
+    );
+
+    client.propose_duration_change(&creator, &circle_id, &86_400);
+    assert_eq!(
+        client.try_cancel_duration_change(&members[0], &circle_id),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+
+    client.cancel_duration_change(&creator, &circle_id);
+    let circle = circle_info(&env, &contract_id, circle_id);
+    assert_eq!(circle.cycle_duration, 604_800);
+    assert_eq!(circle.pending_cycle_duration, 0);
+    assert_eq!(circle.duration_change_effective_at, 0);
+
+    client.propose_duration_change(&creator, &circle_id, &172_800);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).pending_cycle_duration, 172_800);
+}
+
+fn setup_resizable_circle<'a>(env: &Env, max_members: u32) -> (SoroSusuClient<'a>, Address, u64, std::vec::Vec<Address>) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &max_members, &token, &604_800, &0, &0, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(env);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+    // Old enough for governance proposals
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    (client, contract_id, circle_id, members)
+}
+
+fn circle_info(env: &Env, contract_id: &Address, circle_id: u64) -> CircleInfo {
+    env.as_contract(contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap())
+}
+
+#[test]
+fn max_members_growth_applies_after_majority_vote() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+
+    client.propose_max_members_change(&members[0], &circle_id, &8);
+    let circle = circle_info(&env, &contract_id, circle_id);
+    assert_eq!(circle.max_members, 4);
+    assert_eq!(circle.proposed_max_members, 8);
+
+    client.vote_max_members_change(&members[1], &circle_id);
+    let circle = circle_info(&env, &contract_id, circle_id);
+    assert_eq!(circle.max_members, 8);
+    assert_eq!(circle.proposed_max_members, 0);
+    assert_eq!(circle.max_members_votes_bitmap, 0);
+
+    assert_eq!(
+        client.try_vote_max_members_change(&members[2], &circle_id),
+        Err(Ok(SoroSusuError::NoActiveProposal))
+    );
+}
+
+#[test]
+fn max_members_vote_follows_configured_quorum_and_circle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_quorum_threshold(&creator, &circle_id, &10_000);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    let not_a_member = Err(Ok(SoroSusuError::NotAMember));
+    assert_eq!(client.try_propose_max_members_change(&members[0], &other_circle, &8), not_a_member);
+
+    // Unanimity is required, so two of three is not enough
+    client.propose_max_members_change(&members[0], &circle_id, &8);
+    client.vote_max_members_change(&members[1], &circle_id);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).max_members, 4);
+    assert_eq!(client.try_vote_max_members_change(&members[2], &other_circle), not_a_member);
+
+    client.vote_max_members_change(&members[2], &circle_id);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).max_members, 8);
+}
+
+#[test]
+fn max_members_can_shrink_to_member_count() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 6);
+
+    client.propose_max_members_change(&members[0], &circle_id, &3);
+    client.vote_max_members_change(&members[2], &circle_id);
+
+    assert_eq!(circle_info(&env, &contract_id, circle_id).max_members, 3);
+    assert_eq!(
+        client.try_join_circle(&Address::generate(&env), &circle_id, &1),
+        Err(Ok(SoroSusuError::CircleFull))
+    );
+}
+
+#[test]
+fn max_members_cannot_shrink_below_member_count() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 6);
+
+    assert_eq!(
+        client.try_propose_max_members_change(&members[0], &circle_id, &2),
+        Err(Ok(SoroSusuError::MaxMembersBelowMemberCount))
+    );
+    assert_eq!(
+        client.try_propose_max_members_change(&members[0], &circle_id, &65),
+        Err(Ok(SoroSusuError::MaxMembersExceeded))
+    );
+    assert_eq!(circle_info(&env, &contract_id, circle_id).max_members, 6);
+}
+
+#[test]
+fn deadline_extension_applies_after_majority_vote() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+    let deadline = circle_info(&env, &contract_id, circle_id).deadline_timestamp;
+
+    assert_eq!(
+        client.try_propose_deadline_extension(&members[0], &circle_id, &(7 * 24 * 3600 + 1)),
+        Err(Ok(SoroSusuError::InvalidAmount))
+    );
+
+    // 1 of 3 votes leaves the deadline alone, 2 of 3 moves it
+    client.propose_deadline_extension(&members[0], &circle_id, &86_400);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).deadline_timestamp, deadline);
+    client.vote_deadline_extension(&members[1], &circle_id);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).deadline_timestamp, deadline + 86_400);
+
+    // The request is cleared once applied
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::ExtensionRequest(circle_id)));
+    });
+    assert_eq!(client.try_vote_deadline_extension(&members[2], &circle_id), Err(Ok(SoroSusuError::NoActiveProposal)));
+}
+
+#[test]
+fn deadline_is_extended_at_most_once_per_round() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&member1, &10_000);
+    token::StellarAssetClient::new(&env, &token).mint(&member2, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member1, &circle_id, &1);
+    client.join_circle(&member2, &circle_id, &1);
+    let deadline = circle_info(&env, &contract_id, circle_id).deadline_timestamp;
+
+    client.propose_deadline_extension(&member1, &circle_id, &86_400);
+    client.vote_deadline_extension(&member2, &circle_id);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).deadline_timestamp, deadline + 86_400);
+
+    // A passed extension cannot be followed by another in the same round
+    assert_eq!(
+        client.try_propose_deadline_extension(&member2, &circle_id, &86_400),
+        Err(Ok(SoroSusuError::InvalidOperation))
+    );
+
+    client.deposit(&member1, &circle_id);
+    client.deposit(&member2, &circle_id);
+    client.propose_finalize_round(&admin, &circle_id);
+
+    // The next round may be extended again
+    client.propose_deadline_extension(&member2, &circle_id, &86_400);
+}
+
+#[test]
+fn pending_deadline_extension_cannot_be_replaced() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, members) = setup_resizable_circle(&env, 4);
+    let deadline = circle_info(&env, &contract_id, circle_id).deadline_timestamp;
+
+    client.propose_deadline_extension(&members[0], &circle_id, &86_400);
+    assert_eq!(
+        client.try_propose_deadline_extension(&members[1], &circle_id, &(7 * 24 * 3600)),
+        Err(Ok(SoroSusuError::ChangeAlreadyPending))
+    );
+
+    // Voting still applies the original request
+    client.vote_deadline_extension(&members[2], &circle_id);
+    assert_eq!(circle_info(&env, &contract_id, circle_id).deadline_timestamp, deadline + 86_400);
+}
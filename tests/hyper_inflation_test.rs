@@ -1,4 +1,5 @@
 #![cfg(test)]
+#![allow(clippy::absurd_extreme_comparisons)]
 
 #[test]
 fn test_18_decimal_token_extreme_volumes() {
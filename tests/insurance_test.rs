@@ -0,0 +1,399 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Events, Ledger},
+    token, vec, Address, Env, FromVal, IntoVal, Symbol,
+};
+use sorosusu_contracts::{BadgeType, CircleInfo, DataKey, ProposalType, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+fn setup_insured_circle<'a>(env: &Env, insurance_balance: u64, tier: u32) -> (SoroSusuClient<'a>, Address, Address, u64, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let member = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &1_000, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &tier);
+
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &insurance_balance);
+    });
+
+    (client, contract_id, creator, circle_id, member)
+}
+
+fn insurance_balance(env: &Env, contract_id: &Address, circle_id: u64) -> u64 {
+    SoroSusuClient::new(env, contract_id).get_insurance_fund(&circle_id)
+}
+
+#[test]
+fn coverage_requires_tier_adjusted_amount() {
+    let env = Env::default();
+    // Enough for the base amount, not for a tier 2 member
+    let (client, _contract_id, creator, circle_id, member) = setup_insured_circle(&env, 1_500, 2);
+
+    let result = client.try_trigger_insurance_coverage(&creator, &circle_id, &member);
+    assert_eq!(result, Err(Ok(SoroSusuError::InsufficientBalance)));
+}
+
+#[test]
+fn coverage_must_leave_solvency_floor() {
+    let env = Env::default();
+    // Covers 2_000 but would leave only 500 behind
+    let (client, _contract_id, creator, circle_id, member) = setup_insured_circle(&env, 2_500, 2);
+
+    let result = client.try_trigger_insurance_coverage(&creator, &circle_id, &member);
+    assert_eq!(result, Err(Ok(SoroSusuError::SolvencyFloorBreached)));
+}
+
+#[test]
+fn coverage_deducts_tier_adjusted_amount() {
+    let env = Env::default();
+    let (client, contract_id, creator, circle_id, member) = setup_insured_circle(&env, 3_000, 2);
+
+    client.trigger_insurance_coverage(&creator, &circle_id, &member);
+
+    assert_eq!(insurance_balance(&env, &contract_id, circle_id), 1_000);
+}
+
+#[test]
+fn insurance_status_tracks_deposits_and_coverage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    // A tier 20 deposit pays a 2_000 fee, enough to cover one base contribution
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &1_000, &0, &nft_contract);
+    token::StellarAssetClient::new(&env, &token).mint(&member1, &30_000);
+    client.join_circle(&member1, &circle_id, &20);
+    client.join_circle(&member2, &circle_id, &1);
+
+    assert_eq!(client.get_insurance_status(&circle_id), (0, false, 1_000));
+
+    client.deposit(&member1, &circle_id);
+    assert_eq!(client.get_insurance_status(&circle_id), (2_000, false, 1_000));
+
+    client.trigger_insurance_coverage(&creator, &circle_id, &member2);
+    assert_eq!(client.get_insurance_status(&circle_id), (1_000, true, 1_000));
+    assert_eq!(client.get_insurance_fund(&circle_id), 1_000);
+
+    assert_eq!(client.try_get_insurance_status(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn can_trigger_insurance_explains_each_outcome() {
+    let env = Env::default();
+    let reason = |text: &str| Symbol::new(&env, text);
+
+    let (client, contract_id, creator, circle_id, member) = setup_insured_circle(&env, 3_000, 1);
+    assert_eq!(client.can_trigger_insurance(&circle_id, &member), (true, reason("ok")));
+    assert_eq!(client.can_trigger_insurance(&circle_id, &creator), (false, reason("not_a_member")));
+    assert_eq!(insurance_balance(&env, &contract_id, circle_id), 3_000);
+
+    env.as_contract(&contract_id, || {
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.contribution_bitmap = 1;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    });
+    assert_eq!(client.can_trigger_insurance(&circle_id, &member), (false, reason("member_already_contributed")));
+
+    let other = Address::generate(&env);
+    client.join_circle(&other, &circle_id, &1);
+    client.trigger_insurance_coverage(&creator, &circle_id, &other);
+    assert_eq!(client.can_trigger_insurance(&circle_id, &member), (false, reason("already_used")));
+
+    let (client, _contract_id, creator, circle_id, member) = setup_insured_circle(&env, 500, 1);
+    assert_eq!(client.can_trigger_insurance(&circle_id, &member), (false, reason("insufficient_balance")));
+    client.eject_member(&creator, &circle_id, &member);
+    assert_eq!(client.can_trigger_insurance(&circle_id, &member), (false, reason("member_not_active")));
+
+    let (client, _contract_id, _creator, circle_id, member) = setup_insured_circle(&env, 1_500, 1);
+    assert_eq!(client.can_trigger_insurance(&circle_id, &member), (false, reason("solvency_floor_breached")));
+
+    assert_eq!(client.try_can_trigger_insurance(&99, &member), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn multisig_coverage_applies_the_same_rules() {
+    let env = Env::default();
+    let (client, contract_id, _creator, circle_id, member) = setup_insured_circle(&env, 2_500, 2);
+
+    let admin: Address = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Admin).unwrap());
+    let result = client.try_propose_trigger_insurance(&admin, &circle_id, &member);
+    assert_eq!(result, Err(Ok(SoroSusuError::SolvencyFloorBreached)));
+    assert_eq!(insurance_balance(&env, &contract_id, circle_id), 2_500);
+}
+
+#[test]
+fn insurance_claim_history_accumulates_across_rounds() {
+    let env = Env::default();
+    let (client, contract_id, creator, circle_id, member) = setup_insured_circle(&env, 10_000, 1);
+    let admin: Address = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Admin).unwrap());
+    assert_eq!(client.get_insurance_claim_history(&circle_id).len(), 0);
+
+    let first_claim_at = env.ledger().timestamp();
+    client.trigger_insurance_coverage(&creator, &circle_id, &member);
+
+    // Next round: insurance is available again and is used through the admin path
+    env.as_contract(&contract_id, || {
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.contribution_bitmap = 0;
+        circle.is_insurance_used = false;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    });
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+    let second_claim_at = env.ledger().timestamp();
+    client.propose_trigger_insurance(&admin, &circle_id, &member);
+
+    let history = client.get_insurance_claim_history(&circle_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap(), (first_claim_at, member.clone(), 1_000));
+    assert_eq!(history.get(1).unwrap(), (second_claim_at, member.clone(), 1_000));
+    assert_eq!(client.try_get_insurance_claim_history(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn closing_circle_refunds_leftover_insurance_by_contributions() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    // 10% insurance fee on top of a pool seeded with 2_000; a spare seat keeps the circle from closing itself
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &1_000, &0, &nft_contract);
+    token::StellarAssetClient::new(&env, &token).mint(&contract_id, &2_000);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &2_000u64);
+    });
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    for round in 0..3usize {
+        if round == 0 {
+            // The third member defaults once and insurance covers them
+            client.deposit(&members[0], &circle_id);
+            client.deposit(&members[1], &circle_id);
+            client.propose_trigger_insurance(&admin, &circle_id, &members[2]);
+        } else {
+            for member in &members {
+                client.deposit(member, &circle_id);
+            }
+        }
+
+        assert_eq!(client.try_close_circle(&creator, &circle_id), Err(Ok(SoroSusuError::CircleNotComplete)));
+        client.propose_finalize_round(&admin, &circle_id);
+        env.ledger().with_mut(|li| li.timestamp += 86_400);
+        client.claim_pot(&members[round], &circle_id);
+    }
+
+    // 2_000 seeded, 200 + 300 + 300 collected, 1_000 spent on the default
+    assert_eq!(insurance_balance(&env, &contract_id, circle_id), 1_800);
+    assert_eq!(client.try_close_circle(&members[0], &circle_id), Err(Ok(SoroSusuError::Unauthorized)));
+
+    client.close_circle(&creator, &circle_id);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "INSURANCE_REFUNDED"), circle_id).into_val(&env));
+    assert_eq!(<(Address, u64)>::from_val(&env, &data), (members[2].clone(), 450));
+
+    // Deposits of 3, 3 and 2 split the 1_800 pool 675 / 675 / 450
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&members[0]), 10_000 - 3_300 + 3_000 + 675);
+    assert_eq!(token_client.balance(&members[1]), 10_000 - 3_300 + 3_000 + 675);
+    assert_eq!(token_client.balance(&members[2]), 10_000 - 2_200 + 3_000 + 450);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(insurance_balance(&env, &contract_id, circle_id), 0);
+
+    assert_eq!(client.try_close_circle(&creator, &circle_id), Err(Ok(SoroSusuError::CircleClosed)));
+    assert_eq!(client.try_deposit(&members[0], &circle_id), Err(Ok(SoroSusuError::CircleClosed)));
+
+    for member in &members {
+        assert_eq!(client.get_member_badges(member), vec![&env, BadgeType::CircleCompleter]);
+    }
+}
+
+#[test]
+fn cancelled_round_refunds_contributions_but_keeps_fees() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &1_000, &0, &nft_contract);
+    token::StellarAssetClient::new(&env, &token).mint(&contract_id, &2_000);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &2_000u64);
+    });
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    client.deposit(&members[0], &circle_id);
+    client.deposit(&members[1], &circle_id);
+    client.trigger_insurance_coverage(&creator, &circle_id, &members[2]);
+    assert_eq!(client.try_cancel_round(&members[0], &circle_id), Err(Ok(SoroSusuError::Unauthorized)));
+
+    client.cancel_round(&creator, &circle_id);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "ROUND_CANCELLED"), circle_id).into_val(&env));
+    assert_eq!(u64::from_val(&env, &data), 2_000);
+
+    // Contributions come back, the 100 fees stay in the pool and the covered seat is returned to it
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&members[0]), 9_900);
+    assert_eq!(token_client.balance(&members[1]), 9_900);
+    assert_eq!(client.get_insurance_status(&circle_id), (2_200, false, 1_000));
+    assert_eq!(client.get_round_contributors(&circle_id).len(), 0);
+    assert_eq!(client.get_contribution_history(&members[0]).len(), 1);
+
+    // The round can be collected again from scratch
+    client.deposit(&members[0], &circle_id);
+    assert_eq!(token_client.balance(&members[0]), 8_800);
+    assert_eq!(client.get_round_contributors(&circle_id).len(), 1);
+}
+
+#[test]
+fn insurance_fee_change_applies_from_the_next_round() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &1_000, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    assert_eq!(client.try_vote_insurance_fee_change(&members[1], &circle_id), Err(Ok(SoroSusuError::NoActiveProposal)));
+    assert_eq!(
+        client.try_propose_insurance_fee_change(&members[0], &circle_id, &1_001),
+        Err(Ok(SoroSusuError::InvalidBasisPoints))
+    );
+    assert_eq!(client.try_propose_insurance_fee_change(&creator, &circle_id, &500), Err(Ok(SoroSusuError::NotAMember)));
+
+    // 2 of 3 votes pass the proposal, but this round's deposits still pay 10%
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+    client.propose_insurance_fee_change(&members[0], &circle_id, &500);
+    client.vote_insurance_fee_change(&members[1], &circle_id);
+    let token_client = token::Client::new(&env, &token);
+    for member in &members {
+        client.deposit(member, &circle_id);
+        assert_eq!(token_client.balance(member), 10_000 - 1_100);
+    }
+    assert_eq!(client.get_insurance_status(&circle_id).2, 1_000);
+
+    client.propose_finalize_round(&admin, &circle_id);
+    assert_eq!(client.get_insurance_status(&circle_id).2, 500);
+    let outcome = client.get_governance_history(&circle_id).get(0).unwrap();
+    assert_eq!(outcome.proposal_type, ProposalType::InsuranceFee);
+    assert_eq!((outcome.proposed_value, outcome.votes_cast, outcome.approved), (500, 2, true));
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.claim_pot(&members[0], &circle_id);
+
+    client.deposit(&members[1], &circle_id);
+    assert_eq!(token_client.balance(&members[1]), 10_000 - 1_100 - 1_050);
+}
+
+#[test]
+fn insurance_fund_survives_circle_updates() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let payer = Address::generate(&env);
+    let defaulter = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &1_000, &0, &nft_contract);
+    token::StellarAssetClient::new(&env, &token).mint(&payer, &30_000);
+    client.join_circle(&payer, &circle_id, &20);
+    client.join_circle(&defaulter, &circle_id, &1);
+
+    client.deposit(&payer, &circle_id);
+    assert_eq!(client.get_insurance_fund(&circle_id), 2_000);
+
+    // Settings writes save the whole circle and must leave the pool alone
+    client.set_priority_ordering(&creator, &circle_id, &true);
+    client.trigger_insurance_coverage(&creator, &circle_id, &defaulter);
+    client.set_priority_ordering(&creator, &circle_id, &false);
+
+    assert_eq!(client.get_insurance_fund(&circle_id), 1_000);
+    let circle: CircleInfo = env.as_contract(&contract_id, || {
+        env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap()
+    });
+    assert!(circle.is_insurance_used);
+    assert_eq!(token::Client::new(&env, &token).balance(&contract_id), 20_000 + 2_000);
+}
@@ -0,0 +1,1075 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{storage::Instance as _, Address as TestAddress, Events, Ledger},
+    token, vec, Address, Env, FromVal, IntoVal, Symbol,
+};
+use sorosusu_contracts::{decode_nft_token_id, encode_nft_token_id, CircleInfo, DataKey, Member, SoroSusu, SoroSusuClient, SoroSusuError};
+
+// Counts mints and burns so tests can tell which NFT contract a circle used
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(env: Env, _to: Address, _id: u128) {
+        let key = Symbol::new(&env, "minted");
+        let minted: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(minted + 1));
+    }
+    pub fn burn(env: Env, _from: Address, _id: u128) {
+        let key = Symbol::new(&env, "burned");
+        let burned: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(burned + 1));
+    }
+}
+
+fn nft_calls(env: &Env, nft_contract: &Address) -> (u32, u32) {
+    env.as_contract(nft_contract, || {
+        let minted = env.storage().instance().get(&Symbol::new(env, "minted")).unwrap_or(0);
+        let burned = env.storage().instance().get(&Symbol::new(env, "burned")).unwrap_or(0);
+        (minted, burned)
+    })
+}
+
+#[test]
+fn member_circle_is_recorded_on_join() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let first_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let second_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    client.join_circle(&member1, &first_circle, &1);
+    client.join_circle(&member2, &second_circle, &1);
+
+    assert_eq!(client.get_member_circle(&member1), first_circle);
+    assert_eq!(client.get_member_circle(&member2), second_circle);
+}
+
+#[test]
+fn member_circle_moves_to_replacement_on_fill_vacancy() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let exiting = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    client.join_circle(&exiting, &circle_id, &1);
+    client.request_exit(&exiting, &circle_id);
+    client.fill_vacancy(&replacement, &circle_id, &exiting);
+
+    assert_eq!(client.get_member_circle(&replacement), circle_id);
+    assert!(client.try_get_member_circle(&exiting).is_err());
+}
+
+#[test]
+fn member_count_view_tracks_joins_and_exits() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let stored_count = || env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.member_count
+    });
+    assert_eq!(client.get_member_count(&circle_id), 0);
+
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+        assert_eq!(client.get_member_count(&circle_id), stored_count());
+    }
+    assert_eq!(client.get_member_count(&circle_id), 3);
+
+    // An exit keeps the seat until it is filled, and the replacement reuses it
+    client.request_exit(&members[1], &circle_id);
+    assert_eq!(client.get_member_count(&circle_id), stored_count());
+    client.fill_vacancy(&Address::generate(&env), &circle_id, &members[1]);
+    assert_eq!(client.get_member_count(&circle_id), stored_count());
+    client.eject_member(&creator, &circle_id, &members[2]);
+    assert_eq!(client.get_member_count(&circle_id), 3);
+
+    assert_eq!(client.try_get_member_count(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn pending_exits_lists_only_vacated_seats() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &5, &token, &604_800, &0, &0, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..5 {
+        let member = Address::generate(&env);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+    assert_eq!(client.get_pending_exits(&circle_id).len(), 0);
+
+    client.request_exit(&members[3], &circle_id);
+    client.request_exit(&members[1], &circle_id);
+    client.eject_member(&creator, &circle_id, &members[4]);
+
+    assert_eq!(client.get_pending_exits(&circle_id), vec![&env, members[1].clone(), members[3].clone()]);
+    assert_eq!(client.try_get_pending_exits(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn exit_against_a_foreign_circle_is_rejected() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let bystander = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let own_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &own_circle, &1);
+    client.join_circle(&bystander, &other_circle, &1);
+
+    let not_a_member = Err(Ok(SoroSusuError::NotAMember));
+    assert_eq!(client.try_request_exit(&member, &other_circle), not_a_member);
+    assert_eq!(client.try_cancel_exit_request(&member, &other_circle), not_a_member);
+
+    // Even a stray pending exit cannot hand over a seat in a circle the member never sat in
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::PendingExit(other_circle, member.clone()), &true);
+    });
+    client.request_exit(&member, &own_circle);
+    assert_eq!(client.try_fill_vacancy(&replacement, &other_circle, &member), not_a_member);
+
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(other_circle)).unwrap();
+        assert_eq!(circle.member_addresses.get(0).unwrap(), bystander);
+    });
+    assert_eq!(client.get_member_circle(&member), own_circle);
+}
+
+#[test]
+fn fill_vacancy_refunds_total_contributed() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let exiting = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&exiting, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    // Tier 2 member pays twice the base amount
+    client.join_circle(&exiting, &circle_id, &2);
+    client.deposit(&exiting, &circle_id);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&exiting), 8_000);
+
+    client.request_exit(&exiting, &circle_id);
+    client.fill_vacancy(&replacement, &circle_id, &exiting);
+
+    assert_eq!(token_client.balance(&exiting), 10_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    // The refunded deposit no longer counts for the seat, so the replacement owes this round
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.contribution_bitmap, 0);
+        assert!(!env.storage().instance().has(&DataKey::CurrentRoundContribution(circle_id, 0)));
+    });
+    token::StellarAssetClient::new(&env, &token).mint(&replacement, &1_000);
+    client.deposit(&replacement, &circle_id);
+    assert_eq!(token_client.balance(&contract_id), 1_000);
+}
+
+#[test]
+fn fill_vacancy_refunds_nothing_once_the_pot_was_received() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let exiting = Address::generate(&env);
+    let staying = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&exiting, &10_000);
+    token::StellarAssetClient::new(&env, &token).mint(&staying, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&exiting, &circle_id, &1);
+    client.join_circle(&staying, &circle_id, &1);
+
+    // The first seat receives the first pot
+    client.deposit(&exiting, &circle_id);
+    client.deposit(&staying, &circle_id);
+    client.propose_finalize_round(&admin, &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.claim_pot(&exiting, &circle_id);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&exiting), 11_000);
+
+    client.request_exit(&exiting, &circle_id);
+    client.fill_vacancy(&replacement, &circle_id, &exiting);
+
+    assert_eq!(token_client.balance(&exiting), 11_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn fill_vacancy_refund_is_capped_at_the_circles_own_funds() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let bystander = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other_circle = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+    token::StellarAssetClient::new(&env, &token).mint(&bystander, &10_000);
+    client.join_circle(&bystander, &other_circle, &1);
+    client.deposit(&bystander, &other_circle);
+
+    // The first two rounds' pots go to the first two seats
+    for recipient in &members[..2] {
+        for member in &members {
+            client.deposit(member, &circle_id);
+        }
+        client.propose_finalize_round(&admin, &circle_id);
+        env.ledger().with_mut(|li| li.timestamp += 86_400);
+        client.claim_pot(recipient, &circle_id);
+    }
+
+    // Three deposits paid in, but only the open round's is still held for this circle
+    let exiting = members[2].clone();
+    client.deposit(&exiting, &circle_id);
+    client.request_exit(&exiting, &circle_id);
+    client.fill_vacancy(&replacement, &circle_id, &exiting);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&exiting), 8_000);
+    assert_eq!(token_client.balance(&contract_id), 1_000);
+
+    let (_, _, data) = env.events().all().iter()
+        .find(|(_, topics, _)| *topics == (Symbol::new(&env, "PARTIAL_REFUND"), circle_id).into_val(&env))
+        .unwrap();
+    assert_eq!(<(Address, u64, u64)>::from_val(&env, &data), (exiting, 1_000, 3_000));
+}
+
+#[test]
+fn fill_vacancy_without_deposits_refunds_nothing() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let exiting = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    client.join_circle(&exiting, &circle_id, &1);
+    client.request_exit(&exiting, &circle_id);
+    client.fill_vacancy(&replacement, &circle_id, &exiting);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&exiting), 0);
+    assert_eq!(client.get_member_circle(&replacement), circle_id);
+}
+
+fn setup_ejection<'a>(env: &Env, refund_ejected_members: bool) -> (SoroSusuClient<'a>, Address, Address, u64, Address, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let member = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(env, &token).mint(&member, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_refund_ejected_members(&creator, &circle_id, &refund_ejected_members);
+    client.join_circle(&member, &circle_id, &1);
+    client.deposit(&member, &circle_id);
+
+    (client, contract_id, creator, circle_id, member, token)
+}
+
+#[test]
+fn ejected_member_keeps_nothing_without_refund_policy() {
+    let env = Env::default();
+    let (client, contract_id, creator, circle_id, member, token) = setup_ejection(&env, false);
+
+    client.eject_member(&creator, &circle_id, &member);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&member), 9_000);
+    assert_eq!(token_client.balance(&contract_id), 1_000);
+}
+
+#[test]
+fn ejected_member_is_refunded_with_refund_policy() {
+    let env = Env::default();
+    let (client, contract_id, creator, circle_id, member, token) = setup_ejection(&env, true);
+
+    client.eject_member(&creator, &circle_id, &member);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&member), 10_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn ejection_refund_is_capped_at_the_circles_own_funds() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let first = Address::generate(&env);
+    let member = Address::generate(&env);
+    let bystander = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let token_client = token::Client::new(&env, &token);
+
+    env.mock_all_auths();
+    for address in [&first, &member, &bystander] {
+        token::StellarAssetClient::new(&env, &token).mint(address, &10_000);
+    }
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other_circle = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_refund_ejected_members(&creator, &circle_id, &true);
+    client.join_circle(&first, &circle_id, &1);
+    client.join_circle(&member, &circle_id, &1);
+    client.join_circle(&bystander, &other_circle, &1);
+    client.deposit(&bystander, &other_circle);
+
+    // Round one goes to the first seat, so the member's first contribution is already paid out
+    client.deposit(&first, &circle_id);
+    client.deposit(&member, &circle_id);
+    client.propose_finalize_round(&admin, &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.claim_pot(&first, &circle_id);
+    client.deposit(&member, &circle_id);
+
+    client.eject_member(&creator, &circle_id, &member);
+
+    // Only this round's deposit is still held; the other circle's funds are untouched
+    assert_eq!(token_client.balance(&member), 9_000);
+    assert_eq!(token_client.balance(&contract_id), 1_000);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "PARTIAL_REFUND"), circle_id).into_val(&env));
+    assert_eq!(<(Address, u64, u64)>::from_val(&env, &data), (member.clone(), 1_000, 2_000));
+    assert!(!client.get_round_contributors(&circle_id).contains(&member));
+
+    // A member who has had the pot gets nothing back
+    client.eject_member(&creator, &circle_id, &first);
+    assert_eq!(token_client.balance(&first), 11_000);
+}
+
+#[test]
+fn eject_member_rejects_members_of_other_circles() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    assert_eq!(client.try_eject_member(&creator, &other_circle, &member), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(client.try_propose_eject_member(&admin, &other_circle, &member), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(client.get_member_status_counts(&circle_id), (1, 0, 0, 0));
+}
+
+#[test]
+fn sweep_compacts_seats_around_an_ejected_member() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &5, &token, &604_800, &0, &0, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..5 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    // Seats 0, 1 and 3 have paid; seat 2 is ejected
+    client.deposit(&members[0], &circle_id);
+    client.deposit(&members[1], &circle_id);
+    client.deposit(&members[3], &circle_id);
+    client.eject_member(&creator, &circle_id, &members[2]);
+
+    assert_eq!(client.try_sweep_inactive_members(&members[0], &circle_id), Err(Ok(SoroSusuError::Unauthorized)));
+    client.sweep_inactive_members(&creator, &circle_id);
+
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    assert_eq!(circle.member_count, 4);
+    assert_eq!(client.get_member_count(&circle_id), 4);
+    assert_eq!(circle.contribution_bitmap, 0b0111);
+    assert_eq!(
+        circle.member_addresses,
+        vec![&env, members[0].clone(), members[1].clone(), members[3].clone(), members[4].clone()]
+    );
+    for (index, member) in [(2u32, &members[3]), (3, &members[4])] {
+        let member: Member = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Member(member.clone())).unwrap());
+        assert_eq!(member.index, index);
+    }
+    assert_eq!(client.get_round_contributors(&circle_id), vec![&env, members[0].clone(), members[1].clone(), members[3].clone()]);
+
+    // 5 joins, 1 ejection, and seats 3 and 4 reissued as 2 and 3
+    assert_eq!(nft_calls(&env, &nft_contract), (7, 3));
+
+    // The last survivor completes the round on the compacted seats
+    client.deposit(&members[4], &circle_id);
+    client.propose_finalize_round(&admin, &circle_id);
+    assert_eq!(client.get_round_number(&circle_id), 1);
+}
+
+fn setup_invite_only_circle<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, u64) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_invite_only(&creator, &circle_id, &true);
+
+    (client, creator, circle_id)
+}
+
+#[test]
+fn invited_member_can_join_once() {
+    let env = Env::default();
+    let (client, creator, circle_id) = setup_invite_only_circle(&env);
+    let invitee = Address::generate(&env);
+
+    client.issue_invite(&creator, &circle_id, &invitee);
+    client.join_circle(&invitee, &circle_id, &1);
+
+    assert_eq!(client.get_member_circle(&invitee), circle_id);
+}
+
+#[test]
+fn invite_only_circle_rejects_uninvited_member() {
+    let env = Env::default();
+    let (client, _creator, circle_id) = setup_invite_only_circle(&env);
+    let stranger = Address::generate(&env);
+
+    let result = client.try_join_circle(&stranger, &circle_id, &1);
+    assert_eq!(result, Err(Ok(SoroSusuError::InviteRequired)));
+}
+
+#[test]
+fn revoked_invite_cannot_be_used() {
+    let env = Env::default();
+    let (client, creator, circle_id) = setup_invite_only_circle(&env);
+    let invitee = Address::generate(&env);
+
+    client.issue_invite(&creator, &circle_id, &invitee);
+    client.revoke_invite(&creator, &circle_id, &invitee);
+
+    let result = client.try_join_circle(&invitee, &circle_id, &1);
+    assert_eq!(result, Err(Ok(SoroSusuError::InviteRequired)));
+
+    // Only the creator manages invites
+    assert_eq!(client.try_issue_invite(&invitee, &circle_id, &invitee), Err(Ok(SoroSusuError::Unauthorized)));
+}
+
+#[test]
+fn exited_member_can_only_be_purged_after_delay() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let exiting = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    // Active members have nothing to purge
+    client.join_circle(&exiting, &circle_id, &1);
+    assert_eq!(client.try_purge_exited_member(&exiting), Err(Ok(SoroSusuError::PurgeTooEarly)));
+
+    client.request_exit(&exiting, &circle_id);
+    client.fill_vacancy(&replacement, &circle_id, &exiting);
+
+    env.ledger().with_mut(|li| li.timestamp += 30 * 24 * 3600 - 1);
+    assert_eq!(client.try_purge_exited_member(&exiting), Err(Ok(SoroSusuError::PurgeTooEarly)));
+
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    client.purge_exited_member(&exiting);
+
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::Member(exiting.clone())));
+        assert!(!env.storage().instance().has(&DataKey::EjectionTime(exiting.clone())));
+    });
+
+    // The exited address is free to join again
+    client.join_circle(&exiting, &circle_id, &1);
+}
+
+#[test]
+fn ejected_member_is_purged_only_after_their_seat_is_swept() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let staying = Address::generate(&env);
+    let ejected = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&ejected, &circle_id, &1);
+    client.join_circle(&staying, &circle_id, &1);
+
+    client.eject_member(&creator, &circle_id, &ejected);
+    env.ledger().with_mut(|li| li.timestamp += 30 * 24 * 3600);
+    assert_eq!(client.try_purge_exited_member(&ejected), Err(Ok(SoroSusuError::PurgeTooEarly)));
+
+    client.sweep_inactive_members(&creator, &circle_id);
+    client.purge_exited_member(&ejected);
+
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::Member(ejected.clone())));
+        let member: Member = env.storage().instance().get(&DataKey::Member(staying.clone())).unwrap();
+        assert_eq!(member.index, 0);
+    });
+    assert_eq!(client.get_member_status_counts(&circle_id), (1, 0, 0, 0));
+}
+
+#[test]
+fn an_address_sits_in_one_circle_at_a_time() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let first_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let second_circle = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &first_circle, &1);
+
+    assert_eq!(client.try_join_circle(&member, &second_circle, &1), Err(Ok(SoroSusuError::AlreadyMember)));
+
+    // Leaving does not free the address until its exited entry is purged
+    client.request_exit(&member, &first_circle);
+    client.fill_vacancy(&replacement, &first_circle, &member);
+    assert_eq!(client.try_join_circle(&member, &second_circle, &1), Err(Ok(SoroSusuError::AlreadyMember)));
+
+    env.ledger().with_mut(|li| li.timestamp += 30 * 24 * 3600);
+    client.purge_exited_member(&member);
+    client.join_circle(&member, &second_circle, &1);
+    assert_eq!(client.get_member_circle(&member), second_circle);
+
+    // A seated replacement is bound the same way
+    assert_eq!(client.try_join_circle(&replacement, &second_circle, &1), Err(Ok(SoroSusuError::AlreadyMember)));
+}
+
+#[test]
+fn blocked_address_cannot_join_until_unblocked() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let exiting = Address::generate(&env);
+    let bad_actor = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&exiting, &circle_id, &1);
+    client.request_exit(&exiting, &circle_id);
+
+    client.block_address(&admin, &bad_actor);
+    assert_eq!(client.try_join_circle(&bad_actor, &circle_id, &1), Err(Ok(SoroSusuError::AddressBlocked)));
+    assert_eq!(client.try_fill_vacancy(&bad_actor, &circle_id, &exiting), Err(Ok(SoroSusuError::AddressBlocked)));
+
+    client.unblock_address(&admin, &bad_actor);
+    client.join_circle(&bad_actor, &circle_id, &1);
+    assert_eq!(client.get_member_circle(&bad_actor), circle_id);
+}
+
+#[test]
+fn only_protocol_admin_can_block_addresses() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let target = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    assert_eq!(client.try_block_address(&outsider, &target), Err(Ok(SoroSusuError::Unauthorized)));
+    assert_eq!(client.try_unblock_address(&outsider, &target), Err(Ok(SoroSusuError::Unauthorized)));
+}
+
+#[test]
+fn active_member_can_refresh_storage_ttl() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    client.refresh_ttl(&member, &circle_id);
+    let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    assert_eq!(ttl, 365 * 17_280);
+
+    assert_eq!(client.try_refresh_ttl(&creator, &circle_id), Err(Ok(SoroSusuError::NotAMember)));
+    client.request_exit(&member, &circle_id);
+    assert_eq!(client.try_refresh_ttl(&member, &circle_id), Err(Ok(SoroSusuError::MemberNotActive)));
+}
+
+#[test]
+fn cancelled_exit_restores_member_and_closes_vacancy() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    assert_eq!(client.try_cancel_exit_request(&member, &circle_id), Err(Ok(SoroSusuError::NoPendingExit)));
+
+    client.request_exit(&member, &circle_id);
+    assert_eq!(client.try_deposit(&member, &circle_id), Err(Ok(SoroSusuError::MemberNotActive)));
+
+    client.cancel_exit_request(&member, &circle_id);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "EXIT_CANCELLED"), circle_id).into_val(&env));
+    assert_eq!(Address::from_val(&env, &data), member);
+
+    client.deposit(&member, &circle_id);
+    assert_eq!(
+        client.try_fill_vacancy(&replacement, &circle_id, &member),
+        Err(Ok(SoroSusuError::NoPendingExit))
+    );
+}
+
+#[test]
+fn referrals_are_attributed_and_counted() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let referrer = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &5, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other_circle = client.create_circle(&creator, &1_000, &5, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&referrer, &circle_id, &1);
+    client.join_circle(&outsider, &other_circle, &1);
+
+    client.join_circle_with_referrer(&first, &circle_id, &1, &Some(referrer.clone()));
+    client.join_circle_with_referrer(&second, &circle_id, &1, &Some(referrer.clone()));
+
+    assert_eq!(client.get_referrer(&first), Some(referrer.clone()));
+    assert_eq!(client.get_referrer(&referrer), None);
+    assert_eq!(client.get_referral_count(&referrer), 2);
+    assert_eq!(client.get_referral_count(&first), 0);
+
+    // Self-referral and referrers seated in another circle are refused
+    let newcomer = Address::generate(&env);
+    assert_eq!(
+        client.try_join_circle_with_referrer(&newcomer, &circle_id, &1, &Some(newcomer.clone())),
+        Err(Ok(SoroSusuError::InvalidReferrer))
+    );
+    assert_eq!(
+        client.try_join_circle_with_referrer(&newcomer, &circle_id, &1, &Some(outsider.clone())),
+        Err(Ok(SoroSusuError::InvalidReferrer))
+    );
+    assert_eq!(client.get_referral_count(&outsider), 0);
+    assert_eq!(client.try_get_referrer(&newcomer), Err(Ok(SoroSusuError::NotAMember)));
+}
+
+fn setup_batch_circle<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, u64) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_invite_only(&creator, &circle_id, &true);
+
+    (client, creator, circle_id)
+}
+
+#[test]
+fn batch_join_seats_every_member() {
+    let env = Env::default();
+    let (client, creator, circle_id) = setup_batch_circle(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    // No invites needed when the creator seats members directly
+    client.batch_join(&creator, &circle_id, &vec![&env, first.clone(), second.clone()], &vec![&env, 1, 3]);
+
+    assert_eq!(client.get_member_circle(&first), circle_id);
+    assert_eq!(client.get_member_circle(&second), circle_id);
+    assert_eq!(client.simulate_deposit(&second, &circle_id), (3_000, 0, 0));
+
+    assert_eq!(
+        client.try_batch_join(&first, &circle_id, &vec![&env, Address::generate(&env)], &vec![&env, 1]),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_batch_join(&creator, &circle_id, &vec![&env, Address::generate(&env)], &vec![&env, 1, 1]),
+        Err(Ok(SoroSusuError::BatchLengthMismatch))
+    );
+}
+
+#[test]
+fn batch_join_rolls_back_on_any_failure() {
+    let env = Env::default();
+    let (client, creator, circle_id) = setup_batch_circle(&env);
+    let existing = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    client.batch_join(&creator, &circle_id, &vec![&env, existing.clone()], &vec![&env, 1]);
+
+    let result = client.try_batch_join(&creator, &circle_id, &vec![&env, newcomer.clone(), existing.clone()], &vec![&env, 1, 1]);
+    assert_eq!(result, Err(Ok(SoroSusuError::AlreadyMember)));
+
+    // The newcomer seated before the failure was rolled back with the rest of the batch
+    assert_eq!(client.try_get_member_circle(&newcomer), Err(Ok(SoroSusuError::NotAMember)));
+}
+
+#[test]
+fn batch_join_cannot_exceed_max_members() {
+    let env = Env::default();
+    let (client, creator, circle_id) = setup_batch_circle(&env);
+    let mut members = vec![&env];
+    let mut tiers = vec![&env];
+    for _ in 0..5 {
+        members.push_back(Address::generate(&env));
+        tiers.push_back(1u32);
+    }
+
+    assert_eq!(client.try_batch_join(&creator, &circle_id, &members, &tiers), Err(Ok(SoroSusuError::CircleFull)));
+    assert_eq!(client.try_get_member_circle(&members.get(0).unwrap()), Err(Ok(SoroSusuError::NotAMember)));
+}
+
+#[test]
+fn updated_nft_contract_is_used_for_new_mints_and_burns() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    let token = Address::generate(&env);
+    let old_nft = env.register_contract(None, MockNft);
+    let new_nft = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &old_nft);
+    client.join_circle(&early, &circle_id, &1);
+
+    assert_eq!(client.try_set_nft_contract(&early, &circle_id, &new_nft), Err(Ok(SoroSusuError::Unauthorized)));
+    client.set_nft_contract(&creator, &circle_id, &new_nft);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "NFT_CONTRACT_UPDATED"), circle_id).into_val(&env));
+    assert_eq!(<(Address, u32)>::from_val(&env, &data), (new_nft.clone(), 1));
+
+    client.join_circle(&late, &circle_id, &1);
+    client.eject_member(&creator, &circle_id, &early);
+
+    assert_eq!(nft_calls(&env, &old_nft), (1, 0));
+    assert_eq!(nft_calls(&env, &new_nft), (1, 1));
+}
+
+#[test]
+fn circle_reports_full_once_last_seat_is_taken() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    let circle_full = (Symbol::new(&env, "CIRCLE_FULL"), circle_id).into_val(&env);
+    let full_events = |env: &Env| env.events().all().iter().filter(|(_, topics, _)| *topics == circle_full).count();
+
+    client.join_circle(&Address::generate(&env), &circle_id, &1);
+    assert!(!client.is_circle_full(&circle_id));
+    assert_eq!(full_events(&env), 0);
+
+    client.join_circle(&Address::generate(&env), &circle_id, &1);
+    assert_eq!(full_events(&env), 1);
+    assert!(client.is_circle_full(&circle_id));
+
+    // A rejected join does not announce the circle again
+    assert_eq!(client.try_join_circle(&Address::generate(&env), &circle_id, &1), Err(Ok(SoroSusuError::CircleFull)));
+    assert_eq!(full_events(&env), 1);
+    assert_eq!(client.try_is_circle_full(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn approved_tier_change_applies_from_the_next_round() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    let token_client = token::Client::new(&env, &token);
+    token::StellarAssetClient::new(&env, &token).mint(&member1, &10_000);
+    token::StellarAssetClient::new(&env, &token).mint(&member2, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member1, &circle_id, &1);
+    client.join_circle(&member2, &circle_id, &1);
+
+    assert_eq!(client.try_request_tier_change(&member1, &circle_id, &1), Err(Ok(SoroSusuError::InvalidTier)));
+    assert_eq!(client.try_approve_tier_change(&creator, &circle_id, &member1), Err(Ok(SoroSusuError::NoActiveProposal)));
+
+    // Approved mid-round: this round is still paid at tier 1
+    client.request_tier_change(&member1, &circle_id, &2);
+    assert_eq!(client.try_approve_tier_change(&member2, &circle_id, &member1), Err(Ok(SoroSusuError::Unauthorized)));
+    client.approve_tier_change(&creator, &circle_id, &member1);
+    assert_eq!(
+        client.try_request_tier_change(&member1, &circle_id, &3),
+        Err(Ok(SoroSusuError::ChangeAlreadyPending))
+    );
+
+    client.deposit(&member1, &circle_id);
+    client.deposit(&member2, &circle_id);
+    assert_eq!(token_client.balance(&member1), 9_000);
+
+    client.propose_finalize_round(&admin, &circle_id);
+    let tier_changed = (Symbol::new(&env, "TIER_CHANGED"), circle_id).into_val(&env);
+    let (_, _, data) = env.events().all().iter().find(|(_, topics, _)| *topics == tier_changed).unwrap();
+    assert_eq!(<(Address, u32)>::from_val(&env, &data), (member1.clone(), 2));
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.claim_pot(&member1, &circle_id);
+
+    // The new tier is charged from the following round on
+    client.deposit(&member1, &circle_id);
+    client.deposit(&member2, &circle_id);
+    assert_eq!(token_client.balance(&member1), 9_000 + 2_000 - 2_000);
+    assert_eq!(token_client.balance(&member2), 8_000);
+}
+
+#[test]
+fn nft_token_id_round_trips() {
+    for (circle_id, index) in [(0u64, 0u32), (1, 63), (42, 7), (u64::MAX, 63)] {
+        let token_id = encode_nft_token_id(circle_id, index);
+        assert_eq!(decode_nft_token_id(token_id), (circle_id, index));
+    }
+
+    // The circle lives in the high half, the seat in the low half
+    assert_eq!(encode_nft_token_id(1, 2), (1u128 << 64) | 2);
+    assert_ne!(encode_nft_token_id(1, 0), encode_nft_token_id(0, 1));
+}
@@ -0,0 +1,79 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as TestAddress,
+    Address, Env,
+};
+use sorosusu_contracts::{DataKey, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+#[test]
+fn fresh_deployment_starts_at_the_current_version() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    assert_eq!(client.get_contract_version(), 1);
+    client.init(&admin);
+    assert_eq!(client.get_contract_version(), 2);
+
+    // Nothing left to migrate
+    assert_eq!(client.try_migrate(&admin, &2, &3), Err(Ok(SoroSusuError::InvalidOperation)));
+}
+
+#[test]
+fn migration_from_v1_backfills_circle_copies() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    // Roll storage back to what a v1 deployment holds
+    let v1_keys = [DataKey::ContractVersion, DataKey::CircleMemberCount(circle_id), DataKey::CircleToken(circle_id)];
+    env.as_contract(&contract_id, || {
+        for key in v1_keys.iter() {
+            env.storage().instance().remove(key);
+        }
+    });
+    assert_eq!(client.get_contract_version(), 1);
+
+    assert_eq!(client.try_migrate(&creator, &1, &2), Err(Ok(SoroSusuError::Unauthorized)));
+    assert_eq!(client.try_migrate(&admin, &2, &3), Err(Ok(SoroSusuError::InvalidOperation)));
+    assert_eq!(client.try_migrate(&admin, &1, &1), Err(Ok(SoroSusuError::InvalidOperation)));
+    assert_eq!(client.try_migrate(&admin, &1, &3), Err(Ok(SoroSusuError::InvalidOperation)));
+
+    client.migrate(&admin, &1, &2);
+    assert_eq!(client.get_contract_version(), 2);
+    env.as_contract(&contract_id, || {
+        let member_count: u32 = env.storage().instance().get(&DataKey::CircleMemberCount(circle_id)).unwrap();
+        let circle_token: Address = env.storage().instance().get(&DataKey::CircleToken(circle_id)).unwrap();
+        assert_eq!(member_count, 1);
+        assert_eq!(circle_token, token);
+    });
+
+    // The same migration cannot run twice
+    assert_eq!(client.try_migrate(&admin, &1, &2), Err(Ok(SoroSusuError::InvalidOperation)));
+}
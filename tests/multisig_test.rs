@@ -0,0 +1,424 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Events, Ledger},
+    token, vec, Address, Env, FromVal, IntoVal, Symbol,
+};
+use sorosusu_contracts::{CircleInfo, DataKey, Member, MemberStatus, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+#[contract]
+pub struct MockBrokenLendingPool;
+
+#[contractimpl]
+impl MockBrokenLendingPool {
+    pub fn supply(_env: Env, _token: Address, _from: Address, _amount: u64) {}
+    pub fn withdraw(_env: Env, _token: Address, _to: Address, _amount: u64) {
+        panic!("Lending pool unavailable");
+    }
+    pub fn get_lending_pool_version(_env: Env) -> u32 {
+        1
+    }
+}
+
+#[test]
+fn pending_operations_lists_only_unexecuted() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin2 = Address::generate(&env);
+    let admin3 = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    client.set_admin_list(&admin, &vec![&env, admin.clone(), admin2.clone(), admin3.clone()], &2);
+
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member1, &circle_id, &1);
+    client.join_circle(&member2, &circle_id, &1);
+
+    let first = client.propose_eject_member(&admin, &circle_id, &member1);
+    let second = client.propose_eject_member(&admin2, &circle_id, &member2);
+
+    let pending = client.get_pending_operations(&circle_id);
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending.get(0).unwrap().id, first);
+    assert_eq!(pending.get(1).unwrap().id, second);
+
+    // Second approval meets the 2-of-3 threshold
+    client.approve_operation(&admin3, &first, &0);
+
+    let executed = client.get_operation(&first);
+    assert!(executed.is_executed);
+    assert_eq!(executed.approvals.len(), 2);
+
+    let pending = client.get_pending_operations(&circle_id);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().id, second);
+}
+
+#[test]
+fn single_admin_operation_executes_on_proposal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    let operation_id = client.propose_eject_member(&admin, &circle_id, &member);
+
+    assert!(client.get_operation(&operation_id).is_executed);
+    assert_eq!(client.get_pending_operations(&circle_id).len(), 0);
+
+    env.as_contract(&contract_id, || {
+        let ejected: Member = env.storage().instance().get(&DataKey::Member(member.clone())).unwrap();
+        assert_eq!(ejected.status, MemberStatus::Ejected);
+    });
+
+    // Ejected members cannot be ejected twice
+    let result = client.try_propose_eject_member(&admin, &circle_id, &member);
+    assert_eq!(result, Err(Ok(SoroSusuError::MemberNotActive)));
+}
+
+#[test]
+fn admin_cannot_approve_the_same_operation_twice() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin2 = Address::generate(&env);
+    let admin3 = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    client.set_admin_list(&admin, &vec![&env, admin.clone(), admin2.clone(), admin3.clone()], &2);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+    client.join_circle(&other, &circle_id, &1);
+
+    // The proposer's own signature already counts, so a second one cannot reach 2-of-3 alone
+    let first = client.propose_eject_member(&admin, &circle_id, &member);
+    assert_eq!(client.try_approve_operation(&admin, &first, &0), Err(Ok(SoroSusuError::InvalidOperation)));
+    assert_eq!(client.get_operation(&first).approvals, vec![&env, admin.clone()]);
+
+    // A batch skips the operation instead of failing the ids around it
+    let second = client.propose_eject_member(&admin2, &circle_id, &other);
+    client.batch_approve_operations(&admin, &vec![&env, first, second], &0);
+    assert!(!client.get_operation(&first).is_executed);
+    assert!(client.get_operation(&second).is_executed);
+
+    client.approve_operation(&admin3, &first, &0);
+    assert!(client.get_operation(&first).is_executed);
+}
+
+#[test]
+fn non_admin_cannot_propose_operation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    let result = client.try_propose_eject_member(&creator, &circle_id, &member);
+    assert_eq!(result, Err(Ok(SoroSusuError::Unauthorized)));
+}
+
+fn setup_two_of_two<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, Address, u64, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let admin2 = Address::generate(env);
+    let creator = Address::generate(env);
+    let member = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    client.set_admin_list(&admin, &vec![env, admin.clone(), admin2.clone()], &2);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    (client, admin, admin2, circle_id, member)
+}
+
+#[test]
+fn approval_before_expiry_succeeds() {
+    let env = Env::default();
+    let (client, admin, admin2, circle_id, member) = setup_two_of_two(&env);
+
+    let operation_id = client.propose_eject_member(&admin, &circle_id, &member);
+    let operation = client.get_operation(&operation_id);
+    assert_eq!(operation.expiry_timestamp, operation.created_at + 7 * 24 * 3600);
+
+    env.ledger().with_mut(|li| li.timestamp = operation.expiry_timestamp);
+    client.approve_operation(&admin2, &operation_id, &0);
+
+    assert!(client.get_operation(&operation_id).is_executed);
+}
+
+#[test]
+fn approval_after_expiry_panics() {
+    let env = Env::default();
+    let (client, admin, admin2, circle_id, member) = setup_two_of_two(&env);
+
+    let operation_id = client.propose_eject_member(&admin, &circle_id, &member);
+    let operation = client.get_operation(&operation_id);
+
+    env.ledger().with_mut(|li| li.timestamp = operation.expiry_timestamp + 1);
+    let result = client.try_approve_operation(&admin2, &operation_id, &0);
+    assert_eq!(result, Err(Ok(SoroSusuError::OperationExpired)));
+}
+
+#[test]
+fn cancel_expired_operation_removes_it() {
+    let env = Env::default();
+    let (client, admin, _admin2, circle_id, member) = setup_two_of_two(&env);
+
+    let operation_id = client.propose_eject_member(&admin, &circle_id, &member);
+    let operation = client.get_operation(&operation_id);
+
+    // Not yet expired
+    let result = client.try_cancel_expired_operation(&operation_id);
+    assert_eq!(result, Err(Ok(SoroSusuError::OperationNotExpired)));
+
+    env.ledger().with_mut(|li| li.timestamp = operation.expiry_timestamp + 1);
+    client.cancel_expired_operation(&operation_id);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "OPERATION_EXPIRED"), circle_id).into_val(&env));
+    assert_eq!(u64::from_val(&env, &data), operation_id);
+
+    assert_eq!(client.try_get_operation(&operation_id).err(), Some(Ok(SoroSusuError::OperationNotFound)));
+    assert_eq!(client.get_pending_operations(&circle_id).len(), 0);
+}
+
+#[test]
+fn admins_can_zero_yield_stuck_in_broken_pool() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin2 = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockBrokenLendingPool);
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&member, &1_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    client.set_admin_list(&admin, &vec![&env, admin.clone(), admin2.clone()], &2);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+    client.deposit(&member, &circle_id);
+
+    client.deposit_to_yield_pool(&creator, &circle_id, &500);
+    assert!(client.try_prepare_payout_liquidity(&creator, &circle_id).is_err());
+
+    // One signature is not enough to write the funds off
+    let operation_id = client.admin_zero_yield_deposited(&admin, &circle_id);
+    assert!(!client.get_operation(&operation_id).is_executed);
+
+    client.approve_operation(&admin2, &operation_id, &0);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "YIELD_RECOVERY_OVERRIDE"), circle_id).into_val(&env));
+    assert_eq!(u64::from_val(&env, &data), 500);
+
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.yield_deposited, 0);
+    });
+
+    // Nothing left to recall, so payout preparation no longer touches the pool
+    client.prepare_payout_liquidity(&creator, &circle_id);
+    assert!(client.try_admin_zero_yield_deposited(&creator, &circle_id).is_err());
+}
+
+#[test]
+fn zeroing_yield_needs_two_admins_even_at_threshold_one() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin2 = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockBrokenLendingPool);
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&member, &1_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    client.set_admin_list(&admin, &vec![&env, admin.clone(), admin2.clone()], &1);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+    client.deposit(&member, &circle_id);
+    client.deposit_to_yield_pool(&creator, &circle_id, &500);
+
+    let operation_id = client.admin_zero_yield_deposited(&admin, &circle_id);
+    assert!(!client.get_operation(&operation_id).is_executed);
+    assert_eq!(client.get_remaining_approvals_needed(&operation_id), 1);
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.yield_deposited, 500);
+    });
+
+    client.approve_operation(&admin2, &operation_id, &0);
+    assert!(client.get_operation(&operation_id).is_executed);
+    assert_eq!(client.get_remaining_approvals_needed(&operation_id), 0);
+}
+
+#[test]
+fn approval_nonce_blocks_replay() {
+    let env = Env::default();
+    let (client, admin, admin2, circle_id, member) = setup_two_of_two(&env);
+    let other = Address::generate(&env);
+    client.join_circle(&other, &circle_id, &1);
+
+    let first = client.propose_eject_member(&admin, &circle_id, &member);
+    let second = client.propose_eject_member(&admin, &circle_id, &other);
+    assert_eq!(client.get_admin_nonce(&admin2), 0);
+
+    client.approve_operation(&admin2, &first, &0);
+    assert_eq!(client.get_admin_nonce(&admin2), 1);
+
+    // The same signed approval cannot be reused, not even for another operation
+    assert_eq!(client.try_approve_operation(&admin2, &second, &0), Err(Ok(SoroSusuError::InvalidOperation)));
+    assert_eq!(client.try_approve_operation(&admin2, &second, &2), Err(Ok(SoroSusuError::InvalidOperation)));
+    assert!(!client.get_operation(&second).is_executed);
+
+    client.approve_operation(&admin2, &second, &1);
+    assert!(client.get_operation(&second).is_executed);
+    assert_eq!(client.get_admin_nonce(&admin2), 2);
+    assert_eq!(client.get_admin_nonce(&admin), 0);
+}
+
+#[test]
+fn batch_approval_skips_stale_operations() {
+    let env = Env::default();
+    let (client, admin, admin2, circle_id, member) = setup_two_of_two(&env);
+    let other = Address::generate(&env);
+    client.join_circle(&other, &circle_id, &1);
+
+    let first = client.propose_eject_member(&admin, &circle_id, &member);
+    let second = client.propose_eject_member(&admin, &circle_id, &other);
+    let third = client.propose_finalize_round(&admin, &circle_id);
+    client.approve_operation(&admin2, &first, &0);
+    assert_eq!(client.get_pending_operations(&circle_id).len(), 2);
+
+    // `first` already ran and 99 never existed; the rest of the batch still goes through
+    client.batch_approve_operations(&admin2, &vec![&env, first, second, 99], &1);
+
+    let skipped: std::vec::Vec<u64> = env.events().all().iter()
+        .filter(|(_, topics, _)| *topics == (Symbol::new(&env, "SKIPPED_OPERATION"),).into_val(&env))
+        .map(|(_, _, data)| u64::from_val(&env, &data))
+        .collect();
+    assert_eq!(skipped, [first, 99]);
+    assert!(client.get_operation(&second).is_executed);
+    assert!(!client.get_operation(&third).is_executed);
+    assert_eq!(client.get_admin_nonce(&admin2), 2);
+
+    // One nonce covers the whole batch, so the batch cannot be replayed either
+    assert_eq!(
+        client.try_batch_approve_operations(&admin2, &vec![&env, third], &1),
+        Err(Ok(SoroSusuError::InvalidOperation))
+    );
+}
+
+#[test]
+fn remaining_approvals_count_down_to_execution() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin2 = Address::generate(&env);
+    let admin3 = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    client.set_admin_list(&admin, &vec![&env, admin.clone(), admin2.clone(), admin3.clone()], &3);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    let operation_id = client.propose_eject_member(&admin2, &circle_id, &member);
+    assert_eq!(client.get_operation_approvers(&operation_id), vec![&env, admin2.clone()]);
+    assert_eq!(client.get_remaining_approvals_needed(&operation_id), 2);
+
+    client.approve_operation(&admin, &operation_id, &0);
+    assert_eq!(client.get_operation_approvers(&operation_id), vec![&env, admin2.clone(), admin.clone()]);
+    assert_eq!(client.get_remaining_approvals_needed(&operation_id), 1);
+
+    client.approve_operation(&admin3, &operation_id, &0);
+    assert!(client.get_operation(&operation_id).is_executed);
+    assert_eq!(client.get_operation_approvers(&operation_id).len(), 3);
+    assert_eq!(client.get_remaining_approvals_needed(&operation_id), 0);
+
+    // Raising the threshold later does not reopen an executed operation
+    client.set_admin_list(&admin, &vec![&env, admin.clone(), admin2.clone(), admin3.clone(), creator.clone()], &4);
+    assert_eq!(client.get_remaining_approvals_needed(&operation_id), 0);
+
+    assert_eq!(client.try_get_operation_approvers(&99), Err(Ok(SoroSusuError::OperationNotFound)));
+    assert_eq!(client.try_get_remaining_approvals_needed(&99), Err(Ok(SoroSusuError::OperationNotFound)));
+}
@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as TestAddress,
+    token, Address, Env,
+};
+use sorosusu_contracts::{SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+fn setup_paused_circle<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, Address, u64, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let member = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(env, &token).mint(&member, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member, &circle_id, &1);
+
+    client.pause_protocol(&admin);
+
+    (client, admin, creator, circle_id, member)
+}
+
+#[test]
+fn paused_protocol_refuses_deposits_until_resumed() {
+    let env = Env::default();
+    let (client, admin, _creator, circle_id, member) = setup_paused_circle(&env);
+
+    assert_eq!(client.try_deposit(&member, &circle_id), Err(Ok(SoroSusuError::ProtocolPaused)));
+
+    client.resume_protocol(&admin);
+    client.deposit(&member, &circle_id);
+    assert_eq!(client.get_contribution_history(&member).len(), 1);
+}
+
+#[test]
+fn paused_protocol_refuses_new_circles_and_members_but_serves_views() {
+    let env = Env::default();
+    let (client, _admin, creator, circle_id, member) = setup_paused_circle(&env);
+    let paused = Some(Ok(SoroSusuError::ProtocolPaused));
+
+    assert_eq!(client.try_join_circle(&Address::generate(&env), &circle_id, &1).err(), paused);
+    assert_eq!(client.try_clone_circle(&creator, &circle_id).err(), paused);
+    assert_eq!(client.try_request_exit(&member, &circle_id).err(), paused);
+    assert_eq!(client.try_claim_pot(&member, &circle_id).err(), paused);
+    assert_eq!(client.try_prepare_payout_liquidity(&creator, &circle_id).err(), paused);
+
+    assert_eq!(client.get_member_circle(&member), circle_id);
+    assert!(!client.is_circle_full(&circle_id));
+    assert_eq!(client.get_round_contributors(&circle_id).len(), 0);
+}
+
+#[test]
+fn only_admin_can_pause_or_resume() {
+    let env = Env::default();
+    let (client, _admin, creator, _circle_id, _member) = setup_paused_circle(&env);
+
+    assert_eq!(client.try_resume_protocol(&creator), Err(Ok(SoroSusuError::Unauthorized)));
+    assert_eq!(client.try_pause_protocol(&creator), Err(Ok(SoroSusuError::Unauthorized)));
+}
@@ -0,0 +1,585 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Ledger},
+    token, vec, Address, Env,
+};
+use sorosusu_contracts::{BadgeType, CircleInfo, DataKey, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+fn setup_four_member_circle<'a>(env: &Env, priority_ordering: bool) -> (SoroSusuClient<'a>, Address, Address, u64, std::vec::Vec<Address>) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_priority_ordering(&creator, &circle_id, &priority_ordering);
+
+    let mut members = std::vec::Vec::new();
+    for _ in 0..4 {
+        let member = Address::generate(env);
+        token::StellarAssetClient::new(env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    (client, contract_id, admin, circle_id, members)
+}
+
+fn pay_with_last_member_late(env: &Env, client: &SoroSusuClient, circle_id: u64, members: &[Address]) {
+    for member in &members[..3] {
+        client.deposit(member, &circle_id);
+    }
+    env.ledger().with_mut(|li| li.timestamp += 604_800 + 1);
+    client.deposit(&members[3], &circle_id);
+}
+
+fn pot_recipient(env: &Env, contract_id: &Address, circle_id: u64) -> Address {
+    env.as_contract(contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.current_pot_recipient
+    })
+}
+
+#[test]
+fn priority_ordering_pays_lowest_trust_score_first() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, true);
+
+    pay_with_last_member_late(&env, &client, circle_id, &members);
+    client.propose_finalize_round(&admin, &circle_id);
+
+    assert_eq!(pot_recipient(&env, &contract_id, circle_id), members[3]);
+}
+
+#[test]
+fn join_order_is_kept_without_priority_ordering() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    pay_with_last_member_late(&env, &client, circle_id, &members);
+    client.propose_finalize_round(&admin, &circle_id);
+
+    assert_eq!(pot_recipient(&env, &contract_id, circle_id), members[0]);
+}
+
+fn finalize_full_round(client: &SoroSusuClient, admin: &Address, circle_id: u64, members: &[Address]) {
+    for member in members {
+        client.deposit(member, &circle_id);
+    }
+    client.propose_finalize_round(admin, &circle_id);
+}
+
+#[test]
+fn claim_pot_pays_recipient_after_scheduled_time() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    finalize_full_round(&client, &admin, circle_id, &members);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.claim_pot(&members[0], &circle_id);
+
+    let circle_token = env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert!(!circle.is_round_finalized);
+        circle.token
+    });
+    let token_client = token::Client::new(&env, &circle_token);
+    assert_eq!(token_client.balance(&members[0]), 13_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    // The pot can only be claimed once
+    assert_eq!(client.try_claim_pot(&members[0], &circle_id), Err(Ok(SoroSusuError::RoundNotFinalized)));
+}
+
+#[test]
+fn claim_pot_requires_finalized_round() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    client.deposit(&members[0], &circle_id);
+    assert_eq!(client.try_claim_pot(&members[0], &circle_id), Err(Ok(SoroSusuError::RoundNotFinalized)));
+}
+
+#[test]
+fn claim_pot_requires_scheduled_payout_time() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    finalize_full_round(&client, &admin, circle_id, &members);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::ScheduledPayoutTime(circle_id));
+    });
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+
+    assert_eq!(client.try_claim_pot(&members[0], &circle_id), Err(Ok(SoroSusuError::PayoutNotScheduled)));
+}
+
+#[test]
+fn claim_pot_waits_for_scheduled_time() {
+    let env = Env::default();
+    let (client, _contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    finalize_full_round(&client, &admin, circle_id, &members);
+    env.ledger().with_mut(|li| li.timestamp += 86_400 - 1);
+
+    assert_eq!(client.try_claim_pot(&members[0], &circle_id), Err(Ok(SoroSusuError::PayoutTooEarly)));
+}
+
+#[test]
+fn finalize_rejects_corrupted_recipient_index() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    for member in &members {
+        client.deposit(member, &circle_id);
+    }
+
+    // Point the rotation past the last seat
+    env.as_contract(&contract_id, || {
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.current_recipient_index = 7;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    });
+
+    assert_eq!(
+        client.try_propose_finalize_round(&admin, &circle_id),
+        Err(Ok(SoroSusuError::InvalidRecipientIndex))
+    );
+}
+
+#[test]
+fn simulate_payout_reports_finalized_pot() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    for tier in 1..=3u32 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &tier);
+        client.deposit(&member, &circle_id);
+    }
+
+    // Nothing to preview until the round is finalized
+    assert_eq!(client.simulate_payout(&circle_id), (0, 0));
+
+    client.propose_finalize_round(&admin, &circle_id);
+    assert_eq!(client.simulate_payout(&circle_id), (6_000, 6_000));
+}
+
+#[test]
+fn payout_order_skips_members_already_paid() {
+    let env = Env::default();
+    let (client, _contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    assert_eq!(
+        client.get_payout_order(&circle_id),
+        vec![&env, members[0].clone(), members[1].clone(), members[2].clone(), members[3].clone()]
+    );
+
+    finalize_full_round(&client, &admin, circle_id, &members);
+
+    assert_eq!(
+        client.get_payout_order(&circle_id),
+        vec![&env, members[1].clone(), members[2].clone(), members[3].clone()]
+    );
+}
+
+#[test]
+fn payout_order_follows_trust_score_in_priority_mode() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, circle_id, members) = setup_four_member_circle(&env, true);
+
+    pay_with_last_member_late(&env, &client, circle_id, &members);
+
+    // The late payer has the lowest score; ties keep join order
+    assert_eq!(
+        client.get_payout_order(&circle_id),
+        vec![&env, members[3].clone(), members[0].clone(), members[1].clone(), members[2].clone()]
+    );
+}
+
+#[test]
+fn finalize_rejects_empty_circle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    assert_eq!(
+        client.try_propose_finalize_round(&admin, &circle_id),
+        Err(Ok(SoroSusuError::NoActiveMembers))
+    );
+}
+
+#[test]
+fn finalize_rejects_bits_beyond_member_count() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    for member in &members {
+        client.deposit(member, &circle_id);
+    }
+
+    env.as_contract(&contract_id, || {
+        let mut circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.contribution_bitmap |= 1 << 10;
+        env.storage().instance().set(&DataKey::Circle(circle_id), &circle);
+    });
+
+    assert_eq!(
+        client.try_propose_finalize_round(&admin, &circle_id),
+        Err(Ok(SoroSusuError::InvalidContributionBitmap))
+    );
+}
+
+#[test]
+fn deposit_on_behalf_credits_beneficiary() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    let circle_token = env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        circle.token
+    });
+    let token_client = token::Client::new(&env, &circle_token);
+    let payer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &circle_token).mint(&payer, &5_000);
+
+    client.deposit_on_behalf(&payer, &members[0], &circle_id);
+
+    // Only the payer signed
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, payer);
+
+    assert_eq!(token_client.balance(&payer), 4_000);
+    assert_eq!(token_client.balance(&members[0]), 10_000);
+    assert_eq!(client.get_contribution_history(&members[0]).len(), 1);
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.contribution_bitmap, 1);
+    });
+
+    for member in &members[1..] {
+        client.deposit(member, &circle_id);
+    }
+    client.propose_finalize_round(&admin, &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.claim_pot(&members[0], &circle_id);
+    assert_eq!(token_client.balance(&members[0]), 14_000);
+
+    // Outsiders cannot be paid for
+    assert_eq!(
+        client.try_deposit_on_behalf(&payer, &Address::generate(&env), &circle_id),
+        Err(Ok(SoroSusuError::NotAMember))
+    );
+}
+
+#[test]
+fn round_contributors_and_defaulters_split_the_circle() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    // Nobody has paid at the start of the round
+    assert_eq!(client.get_round_contributors(&circle_id).len(), 0);
+    assert_eq!(client.get_round_defaulters(&circle_id).len(), 4);
+
+    client.deposit(&members[2], &circle_id);
+    client.deposit(&members[0], &circle_id);
+
+    assert_eq!(client.get_round_contributors(&circle_id), vec![&env, members[0].clone(), members[2].clone()]);
+    assert_eq!(client.get_round_defaulters(&circle_id), vec![&env, members[1].clone(), members[3].clone()]);
+}
+
+#[test]
+fn round_contribution_summary_shows_mid_round_progress() {
+    let env = Env::default();
+    let (client, _contract_id, _admin, circle_id, members) = setup_four_member_circle(&env, false);
+
+    client.deposit(&members[1], &circle_id);
+    client.deposit(&members[3], &circle_id);
+
+    assert_eq!(
+        client.get_round_contribution_summary(&circle_id),
+        vec![
+            &env,
+            (members[0].clone(), 0u64, false),
+            (members[1].clone(), 1_000u64, true),
+            (members[2].clone(), 0u64, false),
+            (members[3].clone(), 1_000u64, true),
+        ]
+    );
+    assert_eq!(client.try_get_round_contribution_summary(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn circle_stats_accumulate_over_two_rounds() {
+    let env = Env::default();
+    let (client, contract_id, admin, circle_id, members) = setup_four_member_circle(&env, false);
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    let token_client = token::Client::new(&env, &circle.token);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::InsuranceFund(circle_id), &5_000u64);
+    });
+    assert_eq!(client.get_circle_stats(&circle_id).total_members_joined, 4);
+
+    // Round 1: three pay on time and insurance covers the fourth
+    for member in &members[..3] {
+        client.deposit(member, &circle_id);
+    }
+    client.trigger_insurance_coverage(&circle.creator, &circle_id, &members[3]);
+    client.propose_finalize_round(&admin, &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    let before_claim = token_client.balance(&members[0]);
+    client.claim_pot(&members[0], &circle_id);
+    let pot = token_client.balance(&members[0]) - before_claim;
+
+    // Round 2: the fourth member is ejected and one payment comes in late
+    client.eject_member(&circle.creator, &circle_id, &members[3]);
+    client.sweep_inactive_members(&circle.creator, &circle_id);
+    client.deposit(&members[0], &circle_id);
+    client.deposit(&members[2], &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&members[1], &circle_id);
+    client.propose_finalize_round(&admin, &circle_id);
+
+    let paid: i128 = members[..3].iter().map(|member| 10_000 - token_client.balance(member)).sum::<i128>() + pot;
+    let stats = client.get_circle_stats(&circle_id);
+    assert_eq!(stats.total_rounds_completed, 2);
+    assert_eq!(stats.total_volume, paid as u128);
+    assert_eq!(stats.total_late_payments, 1);
+    assert_eq!(stats.total_insurance_triggers, 1);
+    assert_eq!(stats.total_members_joined, 4);
+    assert_eq!(stats.total_members_ejected, 1);
+
+    assert_eq!(client.try_get_circle_stats(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn members_can_deposit_in_different_accepted_tokens() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let usdc_payer = Address::generate(&env);
+    let eurc_payer = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let eurc = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let other = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &usdc).mint(&usdc_payer, &10_000);
+    token::StellarAssetClient::new(&env, &eurc).mint(&eurc_payer, &10_000);
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &usdc, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&usdc_payer, &circle_id, &1);
+    client.join_circle(&eurc_payer, &circle_id, &1);
+
+    // The payout token must stay accepted, and members can only pick accepted tokens
+    assert_eq!(
+        client.try_set_accepted_tokens(&creator, &circle_id, &vec![&env, eurc.clone()]),
+        Err(Ok(SoroSusuError::TokenNotAccepted))
+    );
+    client.set_accepted_tokens(&creator, &circle_id, &vec![&env, usdc.clone(), eurc.clone()]);
+    assert_eq!(
+        client.try_set_deposit_token(&eurc_payer, &circle_id, &other),
+        Err(Ok(SoroSusuError::TokenNotAccepted))
+    );
+    client.set_deposit_token(&eurc_payer, &circle_id, &eurc);
+
+    client.deposit(&usdc_payer, &circle_id);
+    client.deposit(&eurc_payer, &circle_id);
+
+    let usdc_client = token::Client::new(&env, &usdc);
+    let eurc_client = token::Client::new(&env, &eurc);
+    assert_eq!(usdc_client.balance(&contract_id), 1_000);
+    assert_eq!(eurc_client.balance(&contract_id), 1_000);
+
+    client.propose_finalize_round(&admin, &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.claim_pot(&usdc_payer, &circle_id);
+
+    // Each part of the pot is paid in the token it was collected in
+    assert_eq!(usdc_client.balance(&usdc_payer), 10_000);
+    assert_eq!(eurc_client.balance(&usdc_payer), 1_000);
+    assert_eq!(usdc_client.balance(&contract_id), 0);
+    assert_eq!(eurc_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn circle_closes_itself_after_the_last_round() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    let members = [Address::generate(&env), Address::generate(&env)];
+    for member in &members {
+        token::StellarAssetClient::new(&env, &token).mint(member, &10_000);
+        client.join_circle(member, &circle_id, &1);
+    }
+    assert_eq!(client.get_round_number(&circle_id), 0);
+
+    for (round, recipient) in members.iter().enumerate() {
+        finalize_full_round(&client, &admin, circle_id, &members);
+        assert_eq!(client.get_round_number(&circle_id), round as u32 + 1);
+
+        env.ledger().with_mut(|li| li.timestamp += 86_400);
+        client.claim_pot(recipient, &circle_id);
+    }
+
+    let circle: CircleInfo = env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap());
+    assert!(!circle.is_active);
+    assert_eq!(token::Client::new(&env, &token).balance(&contract_id), 0);
+
+    // No third round can be started
+    assert_eq!(client.try_deposit(&members[0], &circle_id), Err(Ok(SoroSusuError::CircleClosed)));
+    assert_eq!(client.try_propose_finalize_round(&admin, &circle_id), Err(Ok(SoroSusuError::CircleClosed)));
+    assert_eq!(client.get_round_number(&circle_id), 2);
+    assert_eq!(client.try_get_round_number(&99), Err(Ok(SoroSusuError::CircleNotFound)));
+}
+
+#[test]
+fn replacement_keeps_the_exiting_members_payout_turn() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let token_client = token::Client::new(&env, &token);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &3, &token, &604_800, &0, &0, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    let replacement = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&replacement, &10_000);
+    client.request_exit(&members[1], &circle_id);
+    client.fill_vacancy(&replacement, &circle_id, &members[1]);
+
+    assert_eq!(
+        client.get_payout_order(&circle_id),
+        vec![&env, members[0].clone(), replacement.clone(), members[2].clone()]
+    );
+    assert_eq!(
+        client.get_join_order(&circle_id),
+        vec![&env, members[0].clone(), members[1].clone(), members[2].clone(), replacement.clone()]
+    );
+
+    let seats = [members[0].clone(), replacement.clone(), members[2].clone()];
+    for recipient in &seats[..2] {
+        for member in &seats {
+            client.deposit(member, &circle_id);
+        }
+        client.propose_finalize_round(&admin, &circle_id);
+        assert_eq!(pot_recipient(&env, &contract_id, circle_id), *recipient);
+        env.ledger().with_mut(|li| li.timestamp += 86_400);
+        client.claim_pot(recipient, &circle_id);
+    }
+
+    // The replacement collected the second pot; the member who left only got their deposits back
+    assert_eq!(token_client.balance(&replacement), 10_000 - 2_000 + 3_000);
+    assert_eq!(token_client.balance(&members[1]), 10_000);
+    assert_eq!(client.get_payout_order(&circle_id), vec![&env, members[2].clone()]);
+}
+
+#[test]
+fn active_members_earn_the_completer_badge_when_the_last_round_closes_the_circle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &2, &token, &604_800, &0, &0, &0, &nft_contract);
+    let mut members = std::vec::Vec::new();
+    for _ in 0..2 {
+        let member = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    for recipient in &members {
+        for member in &members {
+            assert_eq!(client.get_member_badges(member).len(), 0);
+            client.deposit(member, &circle_id);
+        }
+        client.propose_finalize_round(&admin, &circle_id);
+        env.ledger().with_mut(|li| li.timestamp += 86_400);
+        client.claim_pot(recipient, &circle_id);
+    }
+
+    for member in &members {
+        assert_eq!(client.get_member_badges(member), vec![&env, BadgeType::CircleCompleter]);
+    }
+    assert_eq!(client.get_member_badges(&creator).len(), 0);
+}
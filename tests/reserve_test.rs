@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Ledger},
+    token, Address, Env,
+};
+use sorosusu_contracts::{DataKey, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+struct ReserveCircle<'a> {
+    client: SoroSusuClient<'a>,
+    contract_id: Address,
+    admin: Address,
+    creator: Address,
+    token: Address,
+    circle_id: u64,
+    members: std::vec::Vec<Address>,
+}
+
+fn setup_token_circle<'a>(env: &Env, member_count: u32) -> ReserveCircle<'a> {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &5, &token, &604_800, &0, &0, &0, &nft_contract);
+
+    let mut members = std::vec::Vec::new();
+    for _ in 0..member_count {
+        let member = Address::generate(env);
+        token::StellarAssetClient::new(env, &token).mint(&member, &10_000);
+        client.join_circle(&member, &circle_id, &1);
+        members.push(member);
+    }
+
+    ReserveCircle { client, contract_id, admin, creator, token, circle_id, members }
+}
+
+fn circle_reserve(env: &Env, contract_id: &Address, circle_id: u64) -> u64 {
+    env.as_contract(contract_id, || {
+        env.storage().instance().get(&DataKey::CircleReserve(circle_id)).unwrap_or(0)
+    })
+}
+
+#[test]
+fn reserve_is_split_equally_among_on_time_payers() {
+    let env = Env::default();
+    let ReserveCircle { client, contract_id, admin, creator, token, circle_id, members } = setup_token_circle(&env, 3);
+
+    client.deposit(&members[1], &circle_id);
+    client.deposit(&members[2], &circle_id);
+
+    // Member 0 pays two cycles late: 1% of 1_000 goes to the reserve
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&members[0], &circle_id);
+    assert_eq!(circle_reserve(&env, &contract_id, circle_id), 10);
+
+    // Payers are only known once the round is finalized
+    assert_eq!(client.try_distribute_group_reserve(&creator, &circle_id), Err(Ok(SoroSusuError::RoundNotFinalized)));
+    client.propose_finalize_round(&admin, &circle_id);
+
+    client.distribute_group_reserve(&creator, &circle_id);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&members[0]), 8_990);
+    assert_eq!(token_client.balance(&members[1]), 9_005);
+    assert_eq!(token_client.balance(&members[2]), 9_005);
+    assert_eq!(circle_reserve(&env, &contract_id, circle_id), 0);
+}
+
+#[test]
+fn reserve_is_kept_when_everyone_was_late() {
+    let env = Env::default();
+    let ReserveCircle { client, contract_id, admin, creator, token, circle_id, members } = setup_token_circle(&env, 2);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&members[0], &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&members[1], &circle_id);
+    assert_eq!(circle_reserve(&env, &contract_id, circle_id), 20);
+    client.propose_finalize_round(&admin, &circle_id);
+
+    client.distribute_group_reserve(&creator, &circle_id);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&members[0]), 8_990);
+    assert_eq!(token_client.balance(&members[1]), 8_990);
+    assert_eq!(circle_reserve(&env, &contract_id, circle_id), 20);
+}
+
+#[test]
+fn reserve_goes_to_the_finalized_round_and_only_once() {
+    let env = Env::default();
+    let ReserveCircle { client, contract_id, admin, creator, token, circle_id, members } = setup_token_circle(&env, 2);
+
+    client.deposit(&members[1], &circle_id);
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&members[0], &circle_id);
+    client.propose_finalize_round(&admin, &circle_id);
+
+    // Late member 0 pays next round's share early; the finalized round's payers are still who counts
+    client.deposit(&members[0], &circle_id);
+    assert_eq!(client.try_distribute_group_reserve(&members[0], &circle_id), Err(Ok(SoroSusuError::Unauthorized)));
+    client.distribute_group_reserve(&admin, &circle_id);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&members[1]), 9_010);
+    assert_eq!(circle_reserve(&env, &contract_id, circle_id), 0);
+
+    // Another late fee before the next finalization cannot be paid out against the same round again
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&members[1], &circle_id);
+    assert!(circle_reserve(&env, &contract_id, circle_id) > 0);
+    assert_eq!(client.try_distribute_group_reserve(&creator, &circle_id), Err(Ok(SoroSusuError::RoundNotFinalized)));
+}
+
+#[test]
+fn reserves_are_scoped_per_circle() {
+    let env = Env::default();
+    let ReserveCircle { client, contract_id, circle_id, members, .. } = setup_token_circle(&env, 1);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * 604_800);
+    client.deposit(&members[0], &circle_id);
+
+    assert_eq!(circle_reserve(&env, &contract_id, circle_id), 10);
+    assert_eq!(circle_reserve(&env, &contract_id, circle_id + 1), 0);
+}
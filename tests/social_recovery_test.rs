@@ -2,10 +2,10 @@
 
 use soroban_sdk::{
     contract, contractimpl,
-    testutils::Address as TestAddress,
-    Address, Env,
+    testutils::{Address as TestAddress, Events, Ledger, MockAuth, MockAuthInvoke},
+    Address, Env, FromVal, IntoVal, Symbol,
 };
-use sorosusu_contracts::{CircleInfo, DataKey, Member, SoroSusu, SoroSusuClient};
+use sorosusu_contracts::{CircleInfo, DataKey, Member, RecoveryProposal, SoroSusu, SoroSusuClient, SoroSusuError};
 
 #[contract]
 pub struct MockNft;
@@ -17,7 +17,7 @@ impl MockNft {
 }
 
 #[test]
-fn social_recovery_requires_more_than_seventy_percent_votes() {
+fn social_recovery_requires_a_majority_of_votes() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SoroSusu);
     let client = SoroSusuClient::new(&env, &contract_id);
@@ -36,17 +36,18 @@ fn social_recovery_requires_more_than_seventy_percent_votes() {
 
     client.init(&admin);
 
-    let circle_id = client.create_circle(&creator, &1_000, &6, &token, &604_800, &0, &nft_contract);
+    let circle_id = client.create_circle(&creator, &1_000, &6, &token, &604_800, &0, &0, &0, &nft_contract);
 
     client.join_circle(&member1, &circle_id, &1);
     client.join_circle(&member2, &circle_id, &1);
     client.join_circle(&member3, &circle_id, &1);
     client.join_circle(&old_member, &circle_id, &1);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
 
     // Proposal auto-votes for proposer: 1/4 = 25%
     client.propose_address_change(&member1, &circle_id, &old_member, &new_member);
 
-    // Second vote: 2/4 = 50% (still below >70%)
+    // Second vote: 2/4 = 50% (not yet a majority)
     client.vote_for_recovery(&member2, &circle_id);
 
     let old_member_key = DataKey::Member(old_member.clone());
@@ -54,9 +55,14 @@ fn social_recovery_requires_more_than_seventy_percent_votes() {
     env.as_contract(&contract_id, || {
         assert!(env.storage().instance().has(&old_member_key));
         assert!(!env.storage().instance().has(&new_member_key));
+
+        let proposal: RecoveryProposal = env.storage().instance().get(&DataKey::RecoveryProposal(circle_id)).unwrap();
+        assert_eq!(proposal.old_address, old_member.clone());
+        assert_eq!(proposal.new_address, new_member.clone());
+        assert_eq!(proposal.votes_bitmap.count_ones(), 2);
     });
 
-    // Third vote: 3/4 = 75% (passes >70%)
+    // Third vote: 3/4 = 75% (passes the default quorum)
     client.vote_for_recovery(&member3, &circle_id);
 
     env.as_contract(&contract_id, || {
@@ -73,14 +79,51 @@ fn social_recovery_requires_more_than_seventy_percent_votes() {
             .unwrap();
         assert_eq!(recovered_addr, new_member);
 
-        assert!(circle_after.recovery_old_address.is_none());
-        assert!(circle_after.recovery_new_address.is_none());
-        assert_eq!(circle_after.recovery_votes_bitmap, 0);
+        assert!(!env.storage().instance().has(&DataKey::RecoveryProposal(circle_id)));
     });
 }
 
 #[test]
-#[should_panic(expected = "New address is already a member")]
+fn social_recovery_uses_a_stricter_circle_quorum() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let old_member = Address::generate(&env);
+    let new_member = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &6, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.set_quorum_threshold(&creator, &circle_id, &9_000);
+
+    let mut voters = std::vec::Vec::new();
+    for _ in 0..4 {
+        let voter = Address::generate(&env);
+        client.join_circle(&voter, &circle_id, &1);
+        voters.push(voter);
+    }
+    client.join_circle(&old_member, &circle_id, &1);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    // 4/5 = 80% clears a simple majority but not this circle's 90% quorum
+    client.propose_address_change(&voters[0], &circle_id, &old_member, &new_member);
+    for voter in &voters[1..] {
+        client.vote_for_recovery(voter, &circle_id);
+    }
+    assert_eq!(client.get_member_circle(&old_member), circle_id);
+
+    client.vote_for_recovery(&old_member, &circle_id);
+    assert_eq!(client.get_member_circle(&new_member), circle_id);
+    assert_eq!(client.try_get_member_circle(&old_member), Err(Ok(SoroSusuError::NotAMember)));
+}
+
+#[test]
 fn social_recovery_rejects_new_address_if_already_member() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SoroSusu);
@@ -97,11 +140,330 @@ fn social_recovery_rejects_new_address_if_already_member() {
     env.mock_all_auths();
 
     client.init(&admin);
-    let circle_id = client.create_circle(&creator, &1_000, &6, &token, &604_800, &0, &nft_contract);
+    let circle_id = client.create_circle(&creator, &1_000, &6, &token, &604_800, &0, &0, &0, &nft_contract);
 
     client.join_circle(&proposer, &circle_id, &1);
     client.join_circle(&old_member, &circle_id, &1);
     client.join_circle(&existing_member, &circle_id, &1);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    let result = client.try_propose_address_change(&proposer, &circle_id, &old_member, &existing_member);
+    assert_eq!(result, Err(Ok(SoroSusuError::AlreadyMember)));
+}
+
+#[test]
+fn members_must_sit_a_full_cycle_before_proposing_recovery() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let veteran = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    let new_key = Address::generate(&env);
+    let token = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &6, &token, &604_800, &0, &0, &0, &nft_contract);
+    let joined_at = env.ledger().timestamp();
+    client.join_circle(&veteran, &circle_id, &1);
+    assert_eq!(client.get_member_join_time(&veteran), joined_at);
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 - 1);
+    client.join_circle(&newcomer, &circle_id, &1);
+    assert_eq!(client.get_member_join_time(&newcomer), joined_at + 604_800 - 1);
+    assert_eq!(
+        client.try_propose_address_change(&veteran, &circle_id, &newcomer, &new_key),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+
+    // One full cycle after joining the veteran may propose; the newcomer still may not
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    assert_eq!(
+        client.try_propose_address_change(&newcomer, &circle_id, &veteran, &new_key),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+    client.propose_address_change(&veteran, &circle_id, &newcomer, &new_key);
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().instance().has(&DataKey::RecoveryProposal(circle_id)));
+    });
+
+    assert_eq!(client.try_get_member_join_time(&new_key), Err(Ok(SoroSusuError::NotAMember)));
+}
+
+fn setup_member_circle<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, u64, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let member = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&Address::generate(env), &circle_id, &1);
+    client.join_circle(&member, &circle_id, &2);
+
+    (client, contract_id, circle_id, member)
+}
+
+#[test]
+fn member_can_move_their_seat_to_a_new_key() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, old_key) = setup_member_circle(&env);
+    let new_key = Address::generate(&env);
+
+    client.reclaim_nft(&old_key, &new_key, &circle_id);
+
+    assert_eq!(client.get_member_circle(&new_key), circle_id);
+    assert_eq!(client.try_get_member_circle(&old_key), Err(Ok(SoroSusuError::NotAMember)));
+    env.as_contract(&contract_id, || {
+        let member: Member = env.storage().instance().get(&DataKey::Member(new_key.clone())).unwrap();
+        assert_eq!(member.address, new_key);
+        assert_eq!(member.index, 1);
+        assert_eq!(member.tier_multiplier, 2);
+        assert!(!env.storage().instance().has(&DataKey::Member(old_key.clone())));
+
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.member_addresses.get(1).unwrap(), new_key);
+    });
+
+    // The old key no longer holds a seat to move
+    assert_eq!(
+        client.try_reclaim_nft(&old_key, &Address::generate(&env), &circle_id),
+        Err(Ok(SoroSusuError::NotAMember))
+    );
+}
+
+#[test]
+fn reclaim_requires_the_old_key_to_sign() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, victim) = setup_member_circle(&env);
+    let thief = Address::generate(&env);
+
+    // Only the thief signs
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &thief,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "reclaim_nft",
+                args: (victim.clone(), thief.clone(), circle_id).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_reclaim_nft(&victim, &thief, &circle_id);
+    assert!(result.is_err());
+
+    assert_eq!(client.get_member_circle(&victim), circle_id);
+    assert_eq!(client.try_get_member_circle(&thief), Err(Ok(SoroSusuError::NotAMember)));
+}
+
+fn setup_recovery_circle<'a>(env: &Env) -> (SoroSusuClient<'a>, Address, u64, Address, Address, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let member1 = Address::generate(env);
+    let member2 = Address::generate(env);
+    let lost = Address::generate(env);
+    let token = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&member1, &circle_id, &1);
+    client.join_circle(&member2, &circle_id, &1);
+    client.join_circle(&lost, &circle_id, &1);
+
+    (client, contract_id, circle_id, member1, member2, lost)
+}
+
+#[test]
+fn emergency_contact_can_start_recovery_for_a_lost_key() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, member1, _member2, lost) = setup_recovery_circle(&env);
+    let contact = Address::generate(&env);
+    let new_key = Address::generate(&env);
+
+    assert_eq!(client.get_emergency_contact(&lost), None);
+    client.set_emergency_contact(&lost, &contact);
+    assert_eq!(client.get_emergency_contact(&lost), Some(contact.clone()));
+
+    // The contact is not a member and needs no tenure; their proposal counts as the lost seat's vote
+    client.propose_address_change(&contact, &circle_id, &lost, &new_key);
+    env.as_contract(&contract_id, || {
+        let proposal: RecoveryProposal = env.storage().instance().get(&DataKey::RecoveryProposal(circle_id)).unwrap();
+        assert_eq!(proposal.votes_bitmap, 1 << 2);
+    });
+
+    // The rest of the circle still has to agree: 1/3 is not a majority, 2/3 is
+    assert_eq!(client.get_member_circle(&lost), circle_id);
+    client.vote_for_recovery(&member1, &circle_id);
+
+    assert_eq!(client.get_member_circle(&new_key), circle_id);
+    assert_eq!(client.try_get_member_circle(&lost), Err(Ok(SoroSusuError::NotAMember)));
+    assert_eq!(client.get_emergency_contact(&new_key), Some(contact));
+}
+
+#[test]
+fn emergency_contact_slot_cannot_be_used_by_others() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, member1, _member2, lost) = setup_recovery_circle(&env);
+    let contact = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let new_key = Address::generate(&env);
+
+    assert_eq!(client.try_set_emergency_contact(&lost, &lost), Err(Ok(SoroSusuError::InvalidAddress)));
+    assert_eq!(client.try_set_emergency_contact(&stranger, &contact), Err(Ok(SoroSusuError::NotAMember)));
+
+    // A stranger cannot name themselves as someone else's contact
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &stranger,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "set_emergency_contact",
+                args: (lost.clone(), stranger.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_set_emergency_contact(&lost, &stranger);
+    assert!(result.is_err());
+
+    client.set_emergency_contact(&lost, &contact);
+
+    // Only the named contact gets the delegated path, and only for the member who named them
+    assert_eq!(
+        client.try_propose_address_change(&stranger, &circle_id, &lost, &new_key),
+        Err(Ok(SoroSusuError::NotAMember))
+    );
+    assert_eq!(
+        client.try_propose_address_change(&contact, &circle_id, &member1, &new_key),
+        Err(Ok(SoroSusuError::NotAMember))
+    );
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::RecoveryProposal(circle_id)));
+    });
+}
+
+#[test]
+fn recovery_votes_stop_after_seven_days() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, member1, member2, lost) = setup_recovery_circle(&env);
+    let new_key = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    client.propose_address_change(&member1, &circle_id, &lost, &new_key);
+
+    // A live proposal cannot be replaced, which would wipe its votes
+    assert_eq!(
+        client.try_propose_address_change(&member2, &circle_id, &lost, &Address::generate(&env)),
+        Err(Ok(SoroSusuError::ChangeAlreadyPending))
+    );
+    assert_eq!(client.try_cancel_expired_recovery(&circle_id), Err(Ok(SoroSusuError::OperationNotExpired)));
+
+    env.ledger().with_mut(|li| li.timestamp += 7 * 24 * 3600 + 1);
+    assert_eq!(client.try_vote_for_recovery(&member2, &circle_id), Err(Ok(SoroSusuError::ProposalExpired)));
+    assert_eq!(client.get_member_circle(&lost), circle_id);
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().instance().has(&DataKey::RecoveryProposal(circle_id)));
+    });
+}
+
+#[test]
+fn expired_recovery_can_be_cancelled_and_proposed_again() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, member1, member2, lost) = setup_recovery_circle(&env);
+    let new_key = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    assert_eq!(client.try_cancel_expired_recovery(&circle_id), Err(Ok(SoroSusuError::NoActiveProposal)));
+    client.propose_address_change(&member1, &circle_id, &lost, &new_key);
+    env.ledger().with_mut(|li| li.timestamp += 7 * 24 * 3600 + 1);
+
+    client.cancel_expired_recovery(&circle_id);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "RECOVERY_EXPIRED"), circle_id).into_val(&env));
+    assert_eq!(<(Address, Address)>::from_val(&env, &data), (lost.clone(), new_key.clone()));
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::RecoveryProposal(circle_id)));
+    });
+
+    // A fresh proposal starts its own seven days and gathers votes again
+    client.propose_address_change(&member2, &circle_id, &lost, &new_key);
+    assert_eq!(client.get_member_circle(&lost), circle_id);
+    client.vote_for_recovery(&member1, &circle_id);
+    assert_eq!(client.get_member_circle(&new_key), circle_id);
+}
+
+#[test]
+fn recovery_cannot_reach_into_another_circle() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, member1, _member2, lost) = setup_recovery_circle(&env);
+    let contact = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let attacker_key = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+
+    // The attacker sits alone in their own circle, so they would clear any quorum there
+    let other_circle = client.create_circle(&creator, &1_000, &4, &Address::generate(&env), &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&attacker, &other_circle, &1);
+    client.set_emergency_contact(&lost, &contact);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+
+    let not_a_member = Err(Ok(SoroSusuError::NotAMember));
+    assert_eq!(client.try_propose_address_change(&attacker, &other_circle, &lost, &attacker_key), not_a_member);
+    assert_eq!(client.try_propose_address_change(&contact, &other_circle, &lost, &attacker_key), not_a_member);
+    assert_eq!(client.try_propose_address_change(&attacker, &circle_id, &lost, &attacker_key), not_a_member);
+
+    // Nor can they vote on a proposal in a circle they do not sit in
+    client.propose_address_change(&member1, &circle_id, &lost, &Address::generate(&env));
+    assert_eq!(client.try_vote_for_recovery(&attacker, &circle_id), not_a_member);
+
+    assert_eq!(client.get_member_circle(&lost), circle_id);
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(other_circle)).unwrap();
+        assert_eq!(circle.member_addresses.len(), 1);
+        assert_eq!(circle.member_addresses.get(0).unwrap(), attacker);
+    });
+}
+
+#[test]
+fn recovery_proposal_for_another_circles_seat_fails() {
+    let env = Env::default();
+    let (client, contract_id, circle_id, _member1, _member2, lost) = setup_recovery_circle(&env);
+    let attacker = Address::generate(&env);
+    let attacker_key = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let other_circle = client.create_circle(&creator, &1_000, &4, &Address::generate(&env), &604_800, &0, &0, &0, &nft_contract);
+    client.join_circle(&attacker, &other_circle, &1);
+
+    // A proposal filed under the wrong circle must not re-key a seat there
+    env.as_contract(&contract_id, || {
+        let proposal = RecoveryProposal {
+            old_address: lost.clone(),
+            new_address: attacker_key.clone(),
+            votes_bitmap: 0,
+            proposed_at: env.ledger().timestamp(),
+        };
+        env.storage().instance().set(&DataKey::RecoveryProposal(other_circle), &proposal);
+    });
+    assert_eq!(client.try_vote_for_recovery(&attacker, &other_circle), Err(Ok(SoroSusuError::NotAMember)));
 
-    client.propose_address_change(&proposer, &circle_id, &old_member, &existing_member);
+    assert_eq!(client.get_member_circle(&lost), circle_id);
+    assert_eq!(client.get_member_circle(&attacker), other_circle);
 }
@@ -0,0 +1,438 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as TestAddress, Events, Ledger},
+    token, Address, Env, FromVal, IntoVal, Symbol,
+};
+use sorosusu_contracts::{CircleInfo, DataKey, ProtocolConfig, SoroSusu, SoroSusuClient, SoroSusuError};
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(_env: Env, _to: Address, _id: u128) {}
+    pub fn burn(_env: Env, _from: Address, _id: u128) {}
+}
+
+// Pays back principal plus interest out of its own balance: 1% unless a "rate_bps" is stored
+#[contract]
+pub struct MockInterestPool;
+
+#[contractimpl]
+impl MockInterestPool {
+    pub fn supply(_env: Env, _token: Address, _from: Address, _amount: u64) {}
+    pub fn withdraw(env: Env, token: Address, to: Address, amount: u64) {
+        let rate_bps: u64 = env.storage().instance().get(&Symbol::new(&env, "rate_bps")).unwrap_or(100);
+        let repayment = amount + amount * rate_bps / 10_000;
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &to, &(repayment as i128));
+    }
+    pub fn get_lending_pool_version(_env: Env) -> u32 {
+        1
+    }
+}
+
+// Seat `count` new members who each pay the contribution, so the open round holds a pot to supply
+fn fund_open_round(env: &Env, client: &SoroSusuClient, token: &Address, circle_id: u64, count: u32) {
+    for _ in 0..count {
+        let member = Address::generate(env);
+        token::StellarAssetClient::new(env, token).mint(&member, &100_000);
+        client.join_circle(&member, &circle_id, &1);
+        client.deposit(&member, &circle_id);
+    }
+}
+
+#[test]
+fn payout_liquidity_records_interest_earned() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockInterestPool);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&lending_pool, &100_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    let circle_id = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    fund_open_round(&env, &client, &token, circle_id, 2);
+
+    client.deposit_to_yield_pool(&creator, &circle_id, &10_000);
+    assert_eq!(client.get_yield_earnings(&circle_id), 0);
+
+    client.prepare_payout_liquidity(&creator, &circle_id);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "YIELD_EARNED"), circle_id).into_val(&env));
+    assert_eq!(u64::from_val(&env, &data), 100);
+
+    assert_eq!(client.get_yield_earnings(&circle_id), 100);
+    // The mock pool leaves the supplied pot in place, so the repayment comes on top of it
+    assert_eq!(token::Client::new(&env, &token).balance(&contract_id), 20_100);
+}
+
+#[test]
+fn yield_pool_cannot_be_supplied_twice_before_recall() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockInterestPool);
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&lending_pool, &100_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    let circle_id = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    fund_open_round(&env, &client, &token, circle_id, 2);
+    assert!(!client.is_yield_active(&circle_id));
+
+    client.deposit_to_yield_pool(&creator, &circle_id, &10_000);
+    assert!(client.is_yield_active(&circle_id));
+    assert_eq!(
+        client.try_deposit_to_yield_pool(&admin, &circle_id, &5_000),
+        Err(Ok(SoroSusuError::InvalidOperation))
+    );
+
+    client.prepare_payout_liquidity(&creator, &circle_id);
+    assert!(!client.is_yield_active(&circle_id));
+
+    // Once recalled, the next round's idle funds can go back in
+    client.deposit_to_yield_pool(&creator, &circle_id, &5_000);
+    assert!(client.is_yield_active(&circle_id));
+}
+
+fn setup_idle_yield_circle<'a>(env: &Env, idle_secs: u64) -> (SoroSusuClient<'a>, Address, u64, Address, Address) {
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let member1 = Address::generate(env);
+    let member2 = Address::generate(env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockInterestPool);
+    let token_admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    let token_client = token::StellarAssetClient::new(env, &token);
+    token_client.mint(&lending_pool, &100_000);
+    token_client.mint(&member1, &10_000);
+    token_client.mint(&member2, &10_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    let circle_id = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    client.update_yield_idle_threshold(&creator, &circle_id, &idle_secs);
+    fund_open_round(env, &client, &token, circle_id, 2);
+    client.join_circle(&member1, &circle_id, &1);
+    client.join_circle(&member2, &circle_id, &1);
+    client.deposit_to_yield_pool(&creator, &circle_id, &10_000);
+
+    (client, creator, circle_id, member1, member2)
+}
+
+#[test]
+fn two_hour_idle_buffer_recalls_yield_two_hours_out() {
+    let env = Env::default();
+    let (client, _creator, circle_id, member1, _member2) = setup_idle_yield_circle(&env, 7_200);
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 - 7_200);
+    client.deposit(&member1, &circle_id);
+    assert!(!client.is_yield_active(&circle_id));
+    assert_eq!(client.get_yield_earnings(&circle_id), 100);
+}
+
+#[test]
+fn one_hour_idle_buffer_keeps_yield_until_one_hour_out() {
+    let env = Env::default();
+    let (client, _creator, circle_id, member1, member2) = setup_idle_yield_circle(&env, 3_600);
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 - 7_200);
+    client.deposit(&member1, &circle_id);
+    assert!(client.is_yield_active(&circle_id));
+
+    // That deposit pushed the deadline a cycle out
+    env.ledger().with_mut(|li| li.timestamp += 604_800 - 3_600);
+    client.deposit(&member2, &circle_id);
+    assert!(!client.is_yield_active(&circle_id));
+}
+
+#[test]
+fn yield_idle_threshold_must_sit_between_an_hour_and_a_cycle() {
+    let env = Env::default();
+    let (client, creator, circle_id, member1, _member2) = setup_idle_yield_circle(&env, 3_600);
+
+    assert_eq!(
+        client.try_update_yield_idle_threshold(&creator, &circle_id, &3_599),
+        Err(Ok(SoroSusuError::InvalidCycleDuration))
+    );
+    assert_eq!(
+        client.try_update_yield_idle_threshold(&creator, &circle_id, &604_801),
+        Err(Ok(SoroSusuError::InvalidCycleDuration))
+    );
+    assert_eq!(
+        client.try_update_yield_idle_threshold(&member1, &circle_id, &7_200),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+    client.update_yield_idle_threshold(&creator, &circle_id, &604_800);
+}
+
+#[test]
+fn deposits_follow_the_protocol_idle_buffer_unless_the_circle_sets_one() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockInterestPool);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&lending_pool, &100_000);
+    token::StellarAssetClient::new(&env, &token).mint(&member, &10_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    let defaults = client.get_protocol_config();
+    assert_eq!(defaults.yield_liquidity_buffer_secs, 3_600);
+
+    let config = ProtocolConfig { yield_liquidity_buffer_secs: 7_200, ..defaults.clone() };
+    assert_eq!(client.try_set_protocol_config(&creator, &config), Err(Ok(SoroSusuError::Unauthorized)));
+    assert_eq!(
+        client.try_set_protocol_config(&admin, &ProtocolConfig { yield_liquidity_buffer_secs: 0, ..defaults.clone() }),
+        Err(Ok(SoroSusuError::InvalidCycleDuration))
+    );
+    client.set_protocol_config(&admin, &config);
+    assert_eq!(client.get_protocol_config(), config);
+
+    let circle_id = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    fund_open_round(&env, &client, &token, circle_id, 2);
+    client.join_circle(&member, &circle_id, &1);
+    client.deposit_to_yield_pool(&creator, &circle_id, &10_000);
+
+    // Circles may not set a buffer below the protocol's
+    assert_eq!(
+        client.try_update_yield_idle_threshold(&creator, &circle_id, &3_600),
+        Err(Ok(SoroSusuError::InvalidCycleDuration))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 604_800 - 7_200);
+    client.deposit(&member, &circle_id);
+    assert!(!client.is_yield_active(&circle_id));
+}
+
+#[test]
+fn yield_deposit_is_refused_on_pool_version_mismatch() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockInterestPool);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    // The pool reports version 1
+    client.set_lending_pool(&admin, &lending_pool, &2);
+    let circle_id = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    fund_open_round(&env, &client, &token, circle_id, 2);
+
+    assert_eq!(
+        client.try_deposit_to_yield_pool(&creator, &circle_id, &10_000),
+        Err(Ok(SoroSusuError::LendingPoolNotConfigured))
+    );
+    assert_eq!(token::Client::new(&env, &token).balance(&contract_id), 10_000);
+
+    env.as_contract(&contract_id, || {
+        let circle: CircleInfo = env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap();
+        assert_eq!(circle.yield_deposited, 0);
+    });
+}
+
+#[test]
+fn payout_liquidity_without_yield_only_applies_due_duration_change() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let token = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    let circle_id = client.create_circle(&creator, &1_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    env.ledger().with_mut(|li| li.timestamp += 604_800);
+    client.propose_duration_change(&creator, &circle_id, &86_400);
+
+    let circle_info = || -> CircleInfo {
+        env.as_contract(&contract_id, || env.storage().instance().get(&DataKey::Circle(circle_id)).unwrap())
+    };
+    let before = circle_info();
+
+    // Still inside the notice period: nothing changes
+    client.prepare_payout_liquidity(&creator, &circle_id);
+    assert_eq!(circle_info().cycle_duration, 604_800);
+    assert_eq!(circle_info().pending_cycle_duration, 86_400);
+
+    env.ledger().with_mut(|li| li.timestamp = before.duration_change_effective_at);
+    client.prepare_payout_liquidity(&creator, &circle_id);
+
+    let after = circle_info();
+    assert_eq!(after.cycle_duration, 86_400);
+    assert_eq!(after.pending_cycle_duration, 0);
+    assert_eq!(after.duration_change_effective_at, 0);
+    assert_eq!(after.yield_deposited, 0);
+    assert_eq!(after.yield_earned_this_round, before.yield_earned_this_round);
+    assert_eq!(after.deadline_timestamp, before.deadline_timestamp);
+    assert_eq!(after.contribution_bitmap, before.contribution_bitmap);
+}
+
+#[test]
+fn circle_yield_strategy_overrides_global_pool() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let conservative = env.register_contract(None, MockInterestPool);
+    let aggressive = env.register_contract(None, MockInterestPool);
+    env.as_contract(&aggressive, || env.storage().instance().set(&Symbol::new(&env, "rate_bps"), &500u64));
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&conservative, &100_000);
+    token::StellarAssetClient::new(&env, &token).mint(&aggressive, &100_000);
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &conservative, &1);
+    client.approve_lending_pool(&admin, &aggressive);
+    client.approve_lending_pool(&admin, &conservative);
+    let default_circle = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let aggressive_circle = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    fund_open_round(&env, &client, &token, default_circle, 2);
+    fund_open_round(&env, &client, &token, aggressive_circle, 2);
+
+    assert_eq!(
+        client.try_set_circle_yield_strategy(&admin, &aggressive_circle, &aggressive),
+        Err(Ok(SoroSusuError::Unauthorized))
+    );
+    client.set_circle_yield_strategy(&creator, &aggressive_circle, &aggressive);
+
+    client.deposit_to_yield_pool(&creator, &default_circle, &10_000);
+    client.deposit_to_yield_pool(&creator, &aggressive_circle, &10_000);
+
+    // Funds in a pool pin the strategy until they are recalled
+    assert_eq!(
+        client.try_set_circle_yield_strategy(&creator, &aggressive_circle, &conservative),
+        Err(Ok(SoroSusuError::InvalidOperation))
+    );
+
+    client.prepare_payout_liquidity(&creator, &default_circle);
+    client.prepare_payout_liquidity(&creator, &aggressive_circle);
+
+    assert_eq!(client.get_yield_earnings(&default_circle), 100);
+    assert_eq!(client.get_yield_earnings(&aggressive_circle), 500);
+    assert_eq!(token::Client::new(&env, &token).balance(&aggressive), 100_000 - 10_500);
+    assert_eq!(token::Client::new(&env, &token).balance(&conservative), 100_000 - 10_100);
+}
+
+#[test]
+fn yield_strategy_must_be_an_approved_pool() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let pool = env.register_contract(None, MockInterestPool);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &token).mint(&pool, &100_000);
+
+    client.init(&admin);
+    // Only the pool ABI version is configured; the pool itself still has to be approved
+    client.set_lending_pool(&admin, &Address::generate(&env), &1);
+    let circle_id = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    fund_open_round(&env, &client, &token, circle_id, 2);
+
+    assert_eq!(
+        client.try_set_circle_yield_strategy(&creator, &circle_id, &pool),
+        Err(Ok(SoroSusuError::InvalidAddress))
+    );
+    assert_eq!(client.try_approve_lending_pool(&creator, &pool), Err(Ok(SoroSusuError::Unauthorized)));
+
+    client.approve_lending_pool(&admin, &pool);
+    client.set_circle_yield_strategy(&creator, &circle_id, &pool);
+    client.deposit_to_yield_pool(&creator, &circle_id, &10_000);
+    assert!(client.is_yield_active(&circle_id));
+
+    // Once revoked the pool can still hand funds back, but takes no new ones
+    client.revoke_lending_pool(&admin, &pool);
+    client.prepare_payout_liquidity(&creator, &circle_id);
+    assert!(!client.is_yield_active(&circle_id));
+    assert_eq!(
+        client.try_deposit_to_yield_pool(&creator, &circle_id, &10_000),
+        Err(Ok(SoroSusuError::LendingPoolNotConfigured))
+    );
+}
+
+#[test]
+fn yield_supply_is_capped_at_the_circles_own_pot() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let nft_contract = env.register_contract(None, MockNft);
+    let lending_pool = env.register_contract(None, MockInterestPool);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    env.mock_all_auths();
+
+    client.init(&admin);
+    client.set_lending_pool(&admin, &lending_pool, &1);
+    let circle_id = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    let other_circle = client.create_circle(&creator, &5_000, &4, &token, &604_800, &0, &0, &0, &nft_contract);
+    fund_open_round(&env, &client, &token, circle_id, 1);
+    fund_open_round(&env, &client, &token, other_circle, 3);
+
+    // The contract holds 20_000, but only 5_000 of it is this circle's
+    assert_eq!(token::Client::new(&env, &token).balance(&contract_id), 20_000);
+    assert_eq!(
+        client.try_deposit_to_yield_pool(&creator, &circle_id, &5_001),
+        Err(Ok(SoroSusuError::InsufficientBalance))
+    );
+    client.deposit_to_yield_pool(&creator, &circle_id, &5_000);
+    assert!(client.is_yield_active(&circle_id));
+}